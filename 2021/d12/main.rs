@@ -1,7 +1,14 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::env;
+
+use aoclib::diagram::{self, Diagram, Shape};
 
 fn main() {
+    if let Some(text) = diagram::requested(env::args(), || caves(include_str!("input.txt"))) {
+        print!("{text}");
+        return;
+    }
     part1();
     part2();
 }
@@ -52,6 +59,24 @@ fn parse(input: &str) -> HashMap<String, Vec<String>> {
     connections
 }
 
+/// Big caves drawn as boxes, small ones as ellipses and the ends doubled
+fn caves(input: &str) -> Diagram {
+    let mut d = Diagram::undirected();
+    for line in input.lines() {
+        let (left, right) = line.split_once('-').unwrap();
+        for cave in [left, right] {
+            let shape = match cave {
+                "start" | "end" => Shape::DoubleCircle,
+                _ if cave.to_uppercase() == cave => Shape::Box,
+                _ => Shape::Ellipse,
+            };
+            d.node(cave, shape);
+        }
+        d.edge(left, right, None);
+    }
+    d
+}
+
 fn count(curr: String, mappings: &HashMap<String, Vec<String>>, path: &mut Vec<String>) -> i32 {
     let is_lower = curr.to_lowercase() == curr;
 
@@ -107,3 +132,15 @@ fn count2(curr: String, mappings: &HashMap<String, Vec<String>>, smalls: &mut Ve
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caves_diagram() {
+        let d = caves(include_str!("simple.test.txt"));
+        assert_eq!(include_str!("simple.test.dot"), d.to_dot());
+        assert_eq!(include_str!("simple.test.mmd"), d.to_mermaid());
+    }
+}
@@ -5,6 +5,10 @@ use std::{
     time::Instant,
 };
 
+use aoclib::{
+    distance::{Coordinates, Metric, SquaredEuclideanDistance},
+    matrix::rotations_3d,
+};
 use itertools::Itertools;
 
 fn main() {
@@ -239,24 +243,18 @@ fn find_shared_translation(
 }
 
 fn all_rotations() -> HashSet<Rotation> {
-    let start_positions: Vec<Position> = vec![
-        (1, 2, 3).into(), // reference position - right hand, Index Finger forward, mf left, thumb up
-        (2, -1, 3).into(), // I.F left
-        (-2, 1, 3).into(), // I.F right
-        (-1, -2, 3).into(), // I.F back
-        (3, 2, -1).into(), // I.F up
-        (-3, 2, 1).into(), // I.F down
-    ];
-    let mut unique: HashSet<Rotation> = HashSet::new();
-
-    for p in start_positions {
-        let mut p = p.clone();
-        for _ in 0..4 {
-            unique.insert(Rotation([p.x as i8, p.y as i8, p.z as i8]));
-            p = p.rotate_90(Axis::X);
-        }
-    }
-    unique
+    // each row of a rotation matrix picks one signed axis
+    rotations_3d()
+        .into_iter()
+        .map(|m| {
+            let mut axes = [0; 3];
+            for (row, values) in m.rows().iter().enumerate() {
+                let (axis, sign) = values.iter().enumerate().find(|(_, v)| **v != 0).unwrap();
+                axes[row] = (axis as i8 + 1) * *sign as i8;
+            }
+            Rotation(axes)
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -344,9 +342,7 @@ impl Position {
     fn distance(&self, other: &Self) -> Distance {
         Distance {
             positions: (self.clone(), other.clone()),
-            squared_euclidean: i64::pow(self.x - other.x, 2)
-                + i64::pow(self.y - other.y, 2)
-                + i64::pow(self.z - other.z, 2),
+            squared_euclidean: SquaredEuclideanDistance::between(self, other).0,
         }
     }
 
@@ -371,6 +367,12 @@ impl Position {
     }
 }
 
+impl Coordinates for Position {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_ {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
 impl From<(i64, i64, i64)> for Position {
     fn from(coords: (i64, i64, i64)) -> Self {
         Position {
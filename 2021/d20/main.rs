@@ -1,5 +1,10 @@
 use core::panic;
-use std::{collections::HashSet, fmt::Debug, hash::Hash, iter::repeat, time::Instant};
+use std::{collections::HashSet, fmt::Debug, hash::Hash, time::Instant};
+
+use aoclib::{
+    automaton::{Automaton, Cells, Connectivity, SparseGrid},
+    grid::Grid,
+};
 
 fn main() {
     let start = Instant::now();
@@ -10,41 +15,58 @@ fn main() {
 
 fn part1() {
     let input = include_str!("input.txt");
-
-    let image_enhancement_algo = ImageEnhancementBits::new(input.lines().next().unwrap());
-    let input_image = InputImage::new(input.lines().skip(2).collect());
-
-    let mut n = next(&input_image, &image_enhancement_algo);
-    println!("");
-    println!("{:?}", n);
-    println!("");
-    n = next(&n, &image_enhancement_algo);
-
-    println!("");
-    println!("{:?}", n);
-    println!("");
-
-    println!("{} pixels", n.light_pixels.len());
+    println!("{} pixels", count_lit(input, 2));
 }
 
 fn part2() {
     let input = include_str!("input.txt");
+    println!("Part2:\n{}", count_lit(input, 50));
+}
 
+fn count_lit(input: &str, steps: usize) -> usize {
+    enhance(input, steps).expect("the infinite background was left lit")
+}
+
+/// The number of light pixels after enhancing the image `steps` times,
+/// `None` if the infinite background is left lit
+fn enhance(input: &str, steps: usize) -> Option<usize> {
     let image_enhancement_algo = ImageEnhancementBits::new(input.lines().next().unwrap());
-    let input_image = InputImage::new(input.lines().skip(2).collect());
+    let pixels: Grid<bool> = Grid {
+        rows: input
+            .lines()
+            .skip(2)
+            .map(|l| l.chars().map(|c| c == '#').collect())
+            .collect(),
+    };
 
-    let result = repeat(()).take(50).fold(input_image, |input, _| {
-        next(&input, &image_enhancement_algo)
-    });
-    println!("Part2 Image:\n{:?}", result);
-    println!("Part2:\n{:?}", result.light_pixels.len());
+    // the neighbours come in reading order, so the pixel itself is the fifth bit
+    let rule = |light: &bool, neighbours: &[&bool]| {
+        let (before, after) = neighbours.split_at(4);
+        let index = before
+            .iter()
+            .copied()
+            .chain([light])
+            .chain(after.iter().copied())
+            .fold(0, |acc, lit| acc << 1 | *lit as i64);
+        image_enhancement_algo.0.contains(&index)
+    };
+    let mut image = Automaton::new(
+        SparseGrid::from_grid(&pixels, false),
+        Connectivity::Eight,
+        rule,
+    );
+    image.run(steps);
+    image.cells.population(|lit| *lit)
 }
 
+#[allow(dead_code)]
 fn bin_to_i16(s: &str) -> i16 {
     let err = format!("bad input: {}", s);
     i16::from_str_radix(s, 2).expect(&err)
 }
 
+// the original step, which the tests check `enhance` against
+#[allow(dead_code)]
 fn next(input_image: &InputImage, image_enhancement_algo: &ImageEnhancementBits) -> InputImage {
     let mut new_input_image: HashSet<Position> = HashSet::new();
 
@@ -85,6 +107,7 @@ fn next(input_image: &InputImage, image_enhancement_algo: &ImageEnhancementBits)
     }
 }
 
+#[allow(dead_code)]
 #[derive(Clone, PartialEq, Eq)]
 struct InputImage {
     light_pixels: HashSet<Position>,
@@ -101,6 +124,7 @@ struct Position {
     y: i64,
 }
 
+#[allow(dead_code)]
 impl Position {
     fn neighbours(&self) -> Vec<Position> {
         vec![
@@ -144,6 +168,7 @@ impl Position {
     }
 }
 
+#[allow(dead_code)]
 impl InputImage {
     fn new(s: Vec<&str>) -> InputImage {
         let mut light_pixels: HashSet<Position> = HashSet::new();
@@ -221,15 +246,20 @@ impl ImageEnhancementBits {
         ImageEnhancementBits(bits)
     }
 
+    #[allow(dead_code)]
     fn zero_light(&self) -> bool {
         self.0.contains(&0)
     }
 
+    #[allow(dead_code)]
     fn max_light(&self) -> bool {
         self.0.contains(&511)
     }
 }
 
+#[cfg(test)]
+use std::iter::repeat;
+
 #[test]
 fn test_bin() {
     let example = "000100010";
@@ -343,3 +373,13 @@ fn test_correct_count() {
     println!("3351:\n{:?}", result);
     assert_eq!(result.light_pixels.len(), 3351);
 }
+
+#[test]
+fn test_enhance_matches_next() {
+    let input = include_str!("input.test.txt");
+    assert_eq!(Some(35), enhance(input, 2));
+    assert_eq!(Some(3351), enhance(input, 50));
+
+    let input = include_str!("input.txt");
+    assert_eq!(Some(5583), enhance(input, 2));
+}
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use aoclib::cartesian::{Point, Rasterise};
+
 fn main() {
     let input = include_str!("input.txt");
 
@@ -26,7 +28,7 @@ fn main() {
             },
         )
     }) {
-        if !vent_points.insert(vent_point) {
+        if !vent_points.insert(vent_point.clone()) {
             vent_collision.insert(vent_point);
         }
     }
@@ -34,43 +36,15 @@ fn main() {
     println!("vent_collisions: {:?}", vent_collision.len());
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
 #[derive(Debug)]
 struct LineSegment(Point, Point);
 
 impl IntoIterator for LineSegment {
     type Item = Point;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = Rasterise;
 
     fn into_iter(self) -> Self::IntoIter {
-        if self.0.x == self.1.x {
-            let mn = i32::min(self.0.y, self.1.y);
-            let mx = i32::max(self.0.y, self.1.y);
-            let v: Vec<_> = (mn..=mx).map(|i| Point { x: self.0.x, y: i }).collect();
-            v.into_iter()
-        } else if self.0.y == self.1.y {
-            let mn = i32::min(self.0.x, self.1.x);
-            let mx = i32::max(self.0.x, self.1.x);
-            let v: Vec<_> = (mn..=mx).map(|i| Point { x: i, y: self.0.y }).collect();
-            v.into_iter()
-        } else {
-            let x_direction = if self.0.x > self.1.x { -1 } else { 1 };
-            let y_direction = if self.0.y > self.1.y { -1 } else { 1 };
-
-            let diag_len = i32::abs(self.0.x - self.1.x);
-            let v: Vec<_> = (0..=diag_len)
-                .map(|n| Point {
-                    x: self.0.x + n * x_direction,
-                    y: self.0.y + n * y_direction,
-                })
-                .collect();
-            v.into_iter()
-        }
+        self.0.to(self.1).points()
     }
 }
 
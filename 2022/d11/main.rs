@@ -1,5 +1,7 @@
 use std::{collections::HashMap, str::FromStr};
 
+use aoclib::number;
+
 fn main() {
     let input = include_str!("input.txt");
     let part1_result = part1(input);
@@ -310,9 +312,10 @@ fn part2(input: &str) -> usize {
         .map(|monkey| monkey.parse().unwrap())
         .collect();
 
-    let worry_reduction = monkeys
-        .iter()
-        .fold(1, |accum, monkey| accum * monkey.test.test_div_by);
+    // every monkey's test still holds for worry levels kept modulo
+    // the lcm of all the divisors
+    let divisors: Vec<i64> = monkeys.iter().map(|m| m.test.test_div_by).collect();
+    let worry_reduction = number::lcm(&divisors);
 
     let mut counts = vec![0; monkeys.len()];
 
@@ -1,6 +1,9 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
 
-use aoclib::cartesian::{Point, Transform, Vector};
+use aoclib::{
+    cartesian::{Point, Vector},
+    falling::{Floor, SandPile},
+};
 
 fn main() {
     let input = include_str!("input.txt");
@@ -12,38 +15,13 @@ fn main() {
 
 #[derive(Debug, PartialEq)]
 struct Cave {
-    sand: HashSet<Point>,
     rock_paths: Vec<Vector>,
     max_depth: i64,
 }
 
 impl Cave {
-    fn try_add_point(self, p: Point) -> Result<Self, (Point, Self)> {
-        // println!("{:?}", p);
-        // we're swapping down for up, since our axis is positive up, negative down
-        if p.y >= self.max_depth {
-            return Err((p, self));
-        }
-
-        let next: [Transform; 3] = [(0, 1).into(), (-1, 1).into(), (1, 1).into()];
-
-        let mut cave = self;
-
-        for t in next {
-            let next_p = p.transform(&t);
-            let collides_sand = cave.sand.contains(&next_p);
-            let collides = collides_sand || cave.rock_paths.iter().any(|path| next_p.on(path));
-            if !collides {
-                return cave.try_add_point(next_p);
-            }
-        }
-
-        let new_sand_added = cave.sand.insert(p.clone());
-        if !new_sand_added {
-            Err((p.clone(), cave))
-        } else {
-            Ok(cave)
-        }
+    fn sand_pile(&self, floor: Floor) -> SandPile {
+        SandPile::new(self.rock_paths.iter().flat_map(|path| path.points()), floor)
     }
 }
 
@@ -75,52 +53,21 @@ impl FromStr for Cave {
         }
         Ok(Cave {
             rock_paths: vectors,
-            sand: HashSet::new(),
             max_depth,
         })
     }
 }
 
 fn part1(input: &str) -> usize {
-    let mut cave: Cave = input.parse().unwrap();
-    let mut count = 0;
-    loop {
-        let falling_from: Point = (500, 0).into();
-        match cave.try_add_point(falling_from) {
-            Result::Ok(c) => {
-                cave = c;
-                count += 1;
-            }
-            Result::Err(_) => {
-                break;
-            }
-        }
-    }
-    count
+    let cave: Cave = input.parse().unwrap();
+    cave.sand_pile(Floor::Abyss).fill((500, 0).into()).len()
 }
 
 fn part2(input: &str) -> usize {
-    let mut cave: Cave = input.parse().unwrap();
-    cave.rock_paths.push(Vector {
-        start: (i64::MIN, cave.max_depth + 2).into(),
-        end: (i64::MAX, cave.max_depth + 2).into(),
-    });
-    cave.max_depth = cave.max_depth + 2;
-
-    let mut count = 0;
-    loop {
-        let falling_from: Point = (500, 0).into();
-        match cave.try_add_point(falling_from) {
-            Result::Ok(c) => {
-                cave = c;
-                count += 1;
-            }
-            Result::Err(_) => {
-                break;
-            }
-        }
-    }
-    count
+    let cave: Cave = input.parse().unwrap();
+    cave.sand_pile(Floor::Solid(cave.max_depth + 2))
+        .fill((500, 0).into())
+        .len()
 }
 
 #[cfg(test)]
@@ -129,11 +76,11 @@ mod test {
 
     use super::*;
 
-    fn draw(c: &Cave, p: &Plane) {
+    fn draw(c: &Cave, sand: &SandPile, p: &Plane) {
         for y in p.top_left.y..=p.bottom_right.y {
             for x in p.top_left.x..=p.bottom_right.x {
                 let point: Point = (x, y).into();
-                let is_sand = c.sand.contains(&point);
+                let is_sand = sand.rested().contains(&point);
                 let is_wall = !is_sand && c.rock_paths.iter().any(|path| point.on(path));
                 if is_wall {
                     print!("#")
@@ -151,7 +98,6 @@ mod test {
     fn test_parse_cave() {
         let input = include_str!("input.example.txt");
         let expected = Cave {
-            sand: HashSet::new(),
             rock_paths: vec![
                 // line 1
                 ((498, 4).into(), (498, 6).into()).into(),
@@ -170,26 +116,17 @@ mod test {
     #[test]
     fn test_example_input() {
         let input = include_str!("input.example.txt");
-        let mut cave: Cave = input.parse().unwrap();
+        let cave: Cave = input.parse().unwrap();
+        let mut sand = cave.sand_pile(Floor::Abyss);
         let plane = Plane {
             top_left: (490, 0).into(),
             bottom_right: (505, 9).into(),
         };
 
         let mut count = 0;
-        loop {
-            match cave.try_add_point((500, 0).into()) {
-                Result::Ok(c) => {
-                    cave = c;
-                    count += 1;
-                    // println!("{} sand added", count);
-                }
-                Result::Err(_) => {
-                    // println!("overflow at {:?}", overflow);
-                    break;
-                }
-            }
-            draw(&cave, &plane);
+        while sand.drop_from((500, 0).into()).is_some() {
+            count += 1;
+            draw(&cave, &sand, &plane);
             println!()
         }
         assert_eq!(24, count);
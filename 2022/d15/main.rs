@@ -2,11 +2,10 @@ use std::{collections::HashSet, fmt::Debug, str::FromStr, time::Instant};
 
 use aoclib::{
     cartesian::{Plane, Point, Transform},
+    diamond::{self, Diamond},
     distance::{Distance, ManhattenDistance},
 };
 
-use rayon::prelude::*;
-
 fn main() {
     let input = include_str!("input.txt");
     let part1 = part1(input, 2000000);
@@ -34,6 +33,10 @@ impl Sensor {
         ManhattenDistance::from_vector((self.pos.clone(), self.closest_beacon.clone()).into())
     }
 
+    fn coverage(&self) -> Diamond {
+        Diamond::from(self.pos.clone().to(self.closest_beacon.clone()))
+    }
+
     fn y_border_points(&self, y: i64) -> Option<(Point, Point)> {
         let ManhattenDistance(max_axes_delta) = self.beacon_distance();
         let distance_to_y = y - self.pos.y;
@@ -73,18 +76,14 @@ fn part1(input: &str, y: i64) -> i64 {
 
 fn part2(input: &str, max: Point) -> i64 {
     let map: Map = input.parse().unwrap();
-    let result = (0..=max.y).into_par_iter().find_map_any(|y| {
-        let exlusion_cols = map.exclusion_area(y, 0, max.x);
-        if exlusion_cols.exclusion_size <= max.x {
-            let mut to_sort = exlusion_cols.exclusions.clone();
-            to_sort.sort_by(|(_, left), (_, right)| (*left).cmp(right));
-            let x = to_sort[0].1 + 1;
-            Some(x * 4000000 + y)
-        } else {
-            None
-        }
-    });
-    result.unwrap_or(-1)
+    let coverage: Vec<_> = map.sensors.iter().map(Sensor::coverage).collect();
+    let search = Plane {
+        top_left: (0, max.y).into(),
+        bottom_right: (max.x, 0).into(),
+    };
+    diamond::find_uncovered(&coverage, &search)
+        .map(|p| p.x * 4000000 + p.y)
+        .unwrap_or(-1)
 }
 
 impl FromStr for Map {
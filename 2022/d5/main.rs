@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use aoclib::input::FromInput;
+
 fn main() {
     let input = include_str!("input.txt");
     let parsed: Input = input.parse().unwrap();
@@ -19,7 +21,8 @@ struct Stacks {
 #[derive(Debug, PartialEq, Clone)]
 struct Crate(char);
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, FromInput)]
+#[input("move {crate_count} from {from} to {to}")]
 struct Move {
     crate_count: usize,
     from: usize,
@@ -41,14 +44,7 @@ impl FromStr for Input {
             .lines()
             .skip_while(|line| !line.is_empty())
             .skip(1)
-            .map(|line| {
-                let parts: Vec<_> = line.split(" ").collect();
-                Move {
-                    crate_count: parts[1].parse().unwrap(),
-                    from: parts[3].parse().unwrap(),
-                    to: parts[5].parse().unwrap(),
-                }
-            })
+            .map(|line| line.parse::<Move>().unwrap())
             .collect();
 
         let stack_counts = stacks[stacks.len() - 1];
@@ -1,4 +1,9 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
+
+use aoclib::{
+    cartesian::{Point, Transform},
+    rope::Rope,
+};
 
 fn main() {
     let input = include_str!("input.txt");
@@ -8,37 +13,25 @@ fn main() {
 }
 
 fn part1(input: &str) -> usize {
-    let mut head = Head {
-        pos: (0, 0),
-        tail: Some(Box::new(Head {
-            pos: (0, 0),
-            tail: None,
-        })),
-    };
-    let mut move_listener = MoveTracker::default();
-    input
-        .lines()
-        .map(|line| line.parse::<Move>().unwrap())
-        .for_each(|item| head.apply(item, &mut move_listener));
-
-    move_listener.tail_positions.len()
+    tail_positions::<2>(input)
 }
 
 fn part2(input: &str) -> usize {
-    let mut head = depth(10).unwrap();
-    let mut move_listener = MoveTracker::default();
+    tail_positions::<10>(input)
+}
+
+fn tail_positions<const N: usize>(input: &str) -> usize {
+    let mut rope: Rope<N> = Rope::new(Point::new(0, 0));
     input
         .lines()
         .map(|line| line.parse::<Move>().unwrap())
-        .for_each(|item| head.apply(item, &mut move_listener));
+        .for_each(|item| apply(&mut rope, item));
 
-    move_listener.tail_positions.len()
+    rope.visited(N - 1).len()
 }
 
-#[derive(Debug, PartialEq)]
-struct Head {
-    pos: (i32, i32),
-    tail: Option<Box<Head>>,
+fn apply<const N: usize>(rope: &mut Rope<N>, movement: Move) {
+    rope.step(&movement.0, movement.1 as usize);
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -49,97 +42,20 @@ enum Direction {
     R,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct Move(Direction, i32);
-
-trait MoveListener {
-    fn on_tail_move(&mut self, from: &(i32, i32), to: &(i32, i32));
-}
-
-struct NOOP {}
-
-struct MoveTracker {
-    tail_positions: HashSet<(i32, i32)>,
-}
-
-impl Default for MoveTracker {
-    fn default() -> Self {
-        let mut tail_positions = HashSet::new();
-        tail_positions.insert((0, 0));
-        Self { tail_positions }
-    }
-}
-
-impl MoveListener for MoveTracker {
-    fn on_tail_move(&mut self, _: &(i32, i32), to: &(i32, i32)) {
-        self.tail_positions.insert((to.0, to.1));
-    }
-}
-
-impl MoveListener for NOOP {
-    fn on_tail_move(&mut self, _: &(i32, i32), _: &(i32, i32)) {}
-}
-
-impl Head {
-    fn apply<T>(&mut self, movement: Move, move_listener: &mut T)
-    where
-        T: MoveListener,
-    {
-        let amount = movement.1;
-
-        for _ in 0..amount {
-            let new_head_position = match movement.0 {
-                Direction::U => (self.pos.0 + 1, self.pos.1),
-                Direction::D => (self.pos.0 - 1, self.pos.1),
-                Direction::L => (self.pos.0, self.pos.1 - 1),
-                Direction::R => (self.pos.0, self.pos.1 + 1),
-            };
-            self.update_to(new_head_position, move_listener);
-        }
-    }
-
-    fn update_to<T>(&mut self, new_head_position: (i32, i32), move_listener: &mut T)
-    where
-        T: MoveListener,
-    {
-        let old_position = self.pos.clone();
-        self.pos = new_head_position;
-        if let Some(t) = self.tail.as_mut() {
-            let move_deltas = (
-                new_head_position.0 - old_position.0,
-                new_head_position.1 - old_position.1,
-            );
-            let new_head_vs_old_tail =
-                (new_head_position.0 - t.pos.0, new_head_position.1 - t.pos.1);
-
-            let new_row_delta = (t.pos.0 - new_head_position.0).abs();
-            let new_col_delta = (t.pos.1 - new_head_position.1).abs();
-            let distance = new_row_delta + new_col_delta;
-
-            if distance <= 2 && new_row_delta.max(new_col_delta) <= 1 {
-                // still touching
-                return;
-            }
-
-            let new_tail_pos = if t.pos.0 == new_head_position.0 {
-                // still same height so just move width
-                (t.pos.0, t.pos.1 + move_deltas.1.signum())
-            } else if t.pos.1 == new_head_position.1 {
-                // still same width so just move vertically
-                (t.pos.0 + move_deltas.0.signum(), t.pos.1)
-            } else {
-                (
-                    t.pos.0 + new_head_vs_old_tail.0.signum(),
-                    t.pos.1 + new_head_vs_old_tail.1.signum(),
-                )
-            };
-            t.update_to(new_tail_pos, move_listener);
-        } else {
-            move_listener.on_tail_move(&old_position, &new_head_position);
+impl From<&Direction> for Transform {
+    fn from(value: &Direction) -> Self {
+        match value {
+            Direction::U => (0, 1).into(),
+            Direction::D => (0, -1).into(),
+            Direction::L => (-1, 0).into(),
+            Direction::R => (1, 0).into(),
         }
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+struct Move(Direction, i32);
+
 #[derive(Debug, PartialEq)]
 enum ParseMoveError {
     BadInput(String),
@@ -169,226 +85,93 @@ impl FromStr for Move {
     }
 }
 
-fn depth(n: i32) -> Option<Head> {
-    if n == 0 {
-        None
-    } else {
-        Some(Head {
-            pos: (0, 0),
-            tail: depth(n - 1).map(|next| Box::new(next)),
-        })
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn rope(head: (i64, i64), tail: (i64, i64)) -> Rope<2> {
+        Rope::from_knots([head.into(), tail.into()])
+    }
+
     #[test]
     fn test_up_from_origin() {
-        let mut initial = Head {
-            pos: (0, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::U, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (1, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::U, 1));
+
+        assert_eq!(rope((0, 1), (0, 0)).knots(), initial.knots());
     }
 
     #[test]
     fn test_down_from_origin() {
-        let mut initial = Head {
-            pos: (0, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::D, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (-1, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::D, 1));
+
+        assert_eq!(rope((0, -1), (0, 0)).knots(), initial.knots());
     }
 
     #[test]
     fn test_right_from_origin() {
-        let mut initial = Head {
-            pos: (0, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::R, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (0, 1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::R, 1));
+
+        assert_eq!(rope((1, 0), (0, 0)).knots(), initial.knots());
     }
 
     #[test]
     fn test_left_from_origin() {
-        let mut initial = Head {
-            pos: (0, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::R, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (0, 1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::R, 1));
+
+        assert_eq!(rope((1, 0), (0, 0)).knots(), initial.knots());
     }
 
     #[test]
     fn test_left_many_from_origin() {
-        let mut initial = Head {
-            pos: (0, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::R, 5), &mut NOOP {});
-
-        let expected = Head {
-            pos: (0, 5),
-            tail: Some(Box::new(Head {
-                pos: (0, 4),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::R, 5));
+
+        assert_eq!(rope((5, 0), (4, 0)).knots(), initial.knots());
     }
 
     #[test]
     fn test_up_from_same_horizontal() {
-        let mut initial = Head {
-            pos: (1, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::U, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (2, 0),
-            tail: Some(Box::new(Head {
-                pos: (1, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, 1), (0, 0));
+
+        apply(&mut initial, Move(Direction::U, 1));
+
+        assert_eq!(rope((0, 2), (0, 1)).knots(), initial.knots());
     }
 
     #[test]
     fn test_down_from_same_horizontal() {
-        let mut initial = Head {
-            pos: (-1, 0),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::D, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (-2, 0),
-            tail: Some(Box::new(Head {
-                pos: (-1, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((0, -1), (0, 0));
+
+        apply(&mut initial, Move(Direction::D, 1));
+
+        assert_eq!(rope((0, -2), (0, -1)).knots(), initial.knots());
     }
 
     #[test]
     fn test_right_from_same_vertical() {
-        let mut initial = Head {
-            pos: (0, 1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::R, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (0, 2),
-            tail: Some(Box::new(Head {
-                pos: (0, 1),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((1, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::R, 1));
+
+        assert_eq!(rope((2, 0), (1, 0)).knots(), initial.knots());
     }
 
     #[test]
     fn test_left_from_same_vertical() {
-        let mut initial = Head {
-            pos: (0, -1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::L, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (0, -2),
-            tail: Some(Box::new(Head {
-                pos: (0, -1),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((-1, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::L, 1));
+
+        assert_eq!(rope((-2, 0), (-1, 0)).knots(), initial.knots());
     }
 
     #[test]
@@ -400,25 +183,11 @@ mod test {
         // .H...
         // .T...
         // .....
-        let mut initial = Head {
-            pos: (1, 1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::U, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (2, 1),
-            tail: Some(Box::new(Head {
-                pos: (1, 1),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((1, 1), (0, 0));
+
+        apply(&mut initial, Move(Direction::U, 1));
+
+        assert_eq!(rope((1, 2), (1, 1)).knots(), initial.knots());
     }
 
     #[test]
@@ -430,25 +199,11 @@ mod test {
         // .....
         // ...T.
         // ...H.
-        let mut initial = Head {
-            pos: (-1, -1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::D, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (-2, -1),
-            tail: Some(Box::new(Head {
-                pos: (-1, -1),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((-1, -1), (0, 0));
+
+        apply(&mut initial, Move(Direction::D, 1));
+
+        assert_eq!(rope((-1, -2), (-1, -1)).knots(), initial.knots());
     }
 
     #[test]
@@ -460,25 +215,11 @@ mod test {
         // .....
         // .H...
         // T....
-        let mut initial = Head {
-            pos: (0, 1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        initial.apply(Move(Direction::U, 1), &mut NOOP {});
-
-        let expected = Head {
-            pos: (1, 1),
-            tail: Some(Box::new(Head {
-                pos: (0, 0),
-                tail: None,
-            })),
-        };
-
-        assert_eq!(initial, expected);
+        let mut initial = rope((1, 0), (0, 0));
+
+        apply(&mut initial, Move(Direction::U, 1));
+
+        assert_eq!(rope((1, 1), (0, 0)).knots(), initial.knots());
     }
 
     #[test]
@@ -489,43 +230,6 @@ mod test {
         assert_eq!("D 5".parse(), Ok(Move(Direction::D, 5)));
     }
 
-    fn print_trail(w: i32, h: i32, head: &Head, seen: &HashSet<(i32, i32)>) {
-        for i in (-h..h).rev() {
-            for j in -w..w {
-                let mut depth = 0;
-                let mut current_head = head;
-                let mut found = false;
-                loop {
-                    if current_head.pos == (i, j) {
-                        if depth == 0 {
-                            print!("H")
-                        } else {
-                            print!("{}", depth);
-                        }
-                        found = true;
-                        break;
-                    }
-                    if let Some(h) = &current_head.tail {
-                        depth = depth + 1;
-                        current_head = h.as_ref();
-                    } else {
-                        break;
-                    }
-                }
-                if !found {
-                    if (0, 0) == (i, j) {
-                        print!("s");
-                    } else if seen.contains(&(i, j)) {
-                        print!("#");
-                    } else {
-                        print!(".");
-                    }
-                }
-            }
-            println!("");
-        }
-    }
-
     #[test]
     fn test_pt1_example() {
         let input = include_str!("input.example.txt");
@@ -533,44 +237,38 @@ mod test {
     }
 
     #[test]
-    fn depth_n() {
-        let result = depth(2).unwrap();
-
-        assert_eq!(
-            result,
-            Head {
-                pos: (0, 0),
-                tail: Some(Box::new(Head {
-                    pos: (0, 0),
-                    tail: None
-                }))
-            }
-        )
+    fn test_new_rope() {
+        let rope: Rope<10> = Rope::new(Point::new(0, 0));
+
+        assert!(rope.knots().iter().all(|k| *k == Point::new(0, 0)));
+        assert_eq!("H", rope.render());
     }
 
-    fn debug_step(move_line: &str, head: &mut Head, move_listener: &mut MoveTracker) {
+    fn debug_step(move_line: &str, rope: &mut Rope<10>) {
         let m = move_line.parse::<Move>().unwrap();
-        head.apply(m.clone(), move_listener);
+        apply(rope, m.clone());
         println!("positions after {:?}", m);
-        print_trail(6, 6, &head, &move_listener.tail_positions);
+        println!("{}", rope.render());
         println!("");
         println!("");
     }
 
     #[test]
     fn test_smaller_pt2_example() {
-        let mut head = depth(10).unwrap();
-        let mut move_listener = MoveTracker::default();
-
-        debug_step("R 1", &mut head, &mut move_listener);
-        debug_step("R 1", &mut head, &mut move_listener);
-        debug_step("R 1", &mut head, &mut move_listener);
-        debug_step("R 1", &mut head, &mut move_listener);
-
-        debug_step("U 1", &mut head, &mut move_listener);
-        debug_step("U 1", &mut head, &mut move_listener);
-        debug_step("U 1", &mut head, &mut move_listener);
-        debug_step("U 1", &mut head, &mut move_listener);
+        let mut rope = Rope::new(Point::new(0, 0));
+
+        debug_step("R 1", &mut rope);
+        debug_step("R 1", &mut rope);
+        debug_step("R 1", &mut rope);
+        debug_step("R 1", &mut rope);
+
+        debug_step("U 1", &mut rope);
+        debug_step("U 1", &mut rope);
+        debug_step("U 1", &mut rope);
+        debug_step("U 1", &mut rope);
+
+        assert_eq!(&Point::new(4, 4), rope.head());
+        assert_eq!(&Point::new(0, 0), rope.tail());
     }
 
     #[test]
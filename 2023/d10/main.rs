@@ -1,6 +1,6 @@
 use std::{collections::HashSet, i64, str::FromStr, time::Instant};
 
-use aoclib::cartesian::{Plane, Point};
+use aoclib::cartesian::{Plane, Point, Polygon};
 use itertools::Itertools;
 
 fn main() {
@@ -64,80 +64,11 @@ fn part2(txt: &str) -> usize {
     let start_pos = start_pos.expect("must be a start");
 
     let mut seen = HashSet::new();
-    path(&g, start_pos, &plane, &mut seen);
-
-    // check all points
-    let mut count = 0;
-    for row in 0..height {
-        let y = row;
-        for x in 0..width {
-            let p = Point {
-                x: x as i64,
-                y: y as i64,
-            };
-            // if this point isn't on the path
-            // check if it's inside or outside the path
-            if !seen.contains(&p) {
-                let mut edge_crosses = 0;
-
-                // need to keep track of how we first crossed the edge
-                // L---7   => crosses once
-                // F---J   => crossed twice
-                let mut start_edge = None;
-
-                for check_x in x..width {
-                    let check_point: Point = (check_x as i64, y as i64).into();
-                    let pipe_type = g.at(&check_point);
-                    if seen.contains(&check_point) {
-                        match start_edge {
-                            Some(&open) => {
-                                match (open, pipe_type) {
-                                    (Tile::SouthAndEastBend, &Tile::SouthAndWestBend) => {
-                                        edge_crosses += 2;
-                                        start_edge = None;
-                                    }
-                                    (Tile::NorthAndEastBend, &Tile::NorthAndWestBend) => {
-                                        edge_crosses += 2;
-                                        start_edge = None;
-                                    }
-                                    (_, &Tile::Horizonal) => {
-                                        // start_edge = None;
-                                    }
-                                    _ => {
-                                        edge_crosses += 1;
-                                        start_edge = None;
-                                    }
-                                }
-                            }
-                            _ => {
-                                match pipe_type {
-                                    Tile::SouthAndEastBend => {
-                                        start_edge = Some(pipe_type);
-                                    }
-                                    Tile::NorthAndEastBend => {
-                                        start_edge = Some(pipe_type);
-                                    }
-                                    Tile::Horizonal => {
-                                        // start_edge = None;
-                                    }
-                                    _ => {
-                                        edge_crosses += 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                if edge_crosses % 2 != 0 {
-                    // odd, so inside the edges
-                    count += 1;
-                    // println!("{:?} inside with {:?} and {:?}", p, edge_crosses, g.at(&p));
-                }
-            }
-        }
-    }
+    let pipe_loop = path(&g, start_pos, &plane, &mut seen);
 
-    count
+    // every tile of the loop is a vertex, so the tiles
+    // enclosed by the loop are the polygon's interior points
+    Polygon(pipe_loop).interior_points() as usize
 }
 
 fn next<'a>(g: &Grid, next: Point, plane: &Plane, seen: &mut HashSet<Point>) -> Option<Point> {
@@ -192,11 +123,14 @@ fn next<'a>(g: &Grid, next: Point, plane: &Plane, seen: &mut HashSet<Point>) ->
     None
 }
 
-fn path(g: &Grid, start: Point, plane: &Plane, seen: &mut HashSet<Point>) {
+fn path(g: &Grid, start: Point, plane: &Plane, seen: &mut HashSet<Point>) -> Vec<Point> {
     let mut n = start;
+    let mut visited = vec![n.clone()];
     while let Some(x) = next(g, n.clone(), plane, seen) {
+        visited.push(x.clone());
         n = x;
     }
+    visited
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
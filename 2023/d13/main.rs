@@ -1,7 +1,7 @@
 use std::time::Instant;
 
 use aoclib::{
-    grid::{FromChar, Grid},
+    grid::{FromChar, Grid, ToChar},
     input,
 };
 
@@ -27,39 +27,20 @@ fn part2(txt: &str) -> usize {
         .sum()
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, FromChar, ToChar)]
 enum Tile {
+    #[tile('.')]
     Ash,
+    #[tile('#')]
     Rock,
 }
 
-impl std::fmt::Display for Tile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Tile::Ash => f.write_str("."),
-            Tile::Rock => f.write_str("#"),
-        }
-    }
-}
-
 impl Default for Tile {
     fn default() -> Self {
         Tile::Ash
     }
 }
 
-impl FromChar for Tile {
-    type Err = String;
-
-    fn from_char(c: char) -> Result<Self, Self::Err> {
-        match c {
-            '#' => Ok(Tile::Rock),
-            '.' => Ok(Tile::Ash),
-            _ => Err(format!("bad char [{}]", c)),
-        }
-    }
-}
-
 fn score(g: &Grid<Tile>, diffs: usize) -> usize {
     let row = symmetry_index(&g.rows, diffs).unwrap_or(0);
     let col = symmetry_index(&g.transpose().rows, diffs).unwrap_or(0);
@@ -1,6 +1,6 @@
 use std::{str::FromStr, time::Instant};
 
-use aoclib::cartesian::{Direction, Point, Transform};
+use aoclib::cartesian::{Direction, Point, Polygon, Transform};
 
 fn main() {
     let input = include_str!("input.txt");
@@ -27,7 +27,7 @@ fn solve(instructions: Vec<Instruction>) -> i64 {
         last_point = last_point.transform(&i.into());
         points.push(last_point.clone());
     }
-    Polygon(points).area()
+    Polygon(points).enclosed_points()
 }
 
 #[derive(Debug)]
@@ -105,26 +105,6 @@ impl FromStr for Pt2DigPlan {
     }
 }
 
-struct Polygon(Vec<Point>);
-
-impl Polygon {
-    fn area(&self) -> i64 {
-        let first = self.0.iter().next().unwrap();
-        let (area, perim, _) =
-            self.0
-                .iter()
-                .skip(1)
-                .fold((0, 0, first), |(area, perimeter, point), next_point| {
-                    let next_area = area + ((point.x * next_point.y) - (point.y * next_point.x));
-                    let next_perim =
-                        perimeter + point.x.abs_diff(next_point.x) + point.y.abs_diff(next_point.y);
-                    (next_area, next_perim, next_point)
-                });
-
-        (area.abs() + perim as i64) / 2 + 1
-    }
-}
-
 #[cfg(test)]
 mod tests {
 
@@ -1,214 +1,98 @@
-use std::{collections::HashMap, str::FromStr, time::Instant};
+use std::{env, str::FromStr, time::Instant};
 
+use aoclib::diagram::{self, Diagram, Shape};
 use aoclib::input;
-use aoclib::range::*;
+use aoclib::range::Range;
+use aoclib::workflow::{Condition, Record, Region, Rule, Workflows};
 
 fn main() {
     let input = include_str!("input.txt");
+    if let Some(text) = diagram::requested(env::args(), || workflow_diagram(input)) {
+        print!("{text}");
+        return;
+    }
     let now = Instant::now();
     println!("part1: {}", part1(input));
     println!("part2: {}", part2(input));
     println!("{}ms", now.elapsed().as_millis());
 }
 
+const START: &str = "in";
+const ACCEPT: &str = "A";
+const REJECT: &str = "R";
+
 fn part1(txt: &str) -> usize {
     let sections: Vec<_> = input::empty_line_chunks(txt).collect();
+    let workflows = parse_workflows(sections[0]);
 
-    let workflows: HashMap<String, Workflow> = sections[0]
+    sections[1]
         .lines()
-        .map(|l| l.parse::<Workflow>().unwrap())
-        .map(|wf| (wf.name.clone(), wf))
-        .collect();
-
-    let mut sum = 0;
-    let ratings = sections[1].lines().map(|l| l.parse::<Rating>().unwrap());
-    let initial = "in".to_owned();
-
-    for rating in ratings {
-        let mut workflow = workflows.get(&initial).expect("in");
-        while let Some(next) = workflow.rules.iter().find_map(|r| rating.apply(r)) {
-            match next {
-                Target::Workflow(wf) => {
-                    workflow = workflows.get(&wf).expect(&format!("workflow {wf}"));
-                }
-                Target::Accept => {
-                    sum += rating.total();
-                    break;
-                }
-                Target::Reject => {
-                    break;
-                }
-            }
-        }
-    }
-
-    sum
+        .map(|l| l.parse::<Rating>().unwrap())
+        .filter(|rating| workflows.evaluate(START, rating.0.clone()).as_deref() == Some(ACCEPT))
+        .map(|rating| rating.total())
+        .sum()
 }
 
 fn part2(txt: &str) -> usize {
     let sections: Vec<_> = input::empty_line_chunks(txt).collect();
+    let workflows = parse_workflows(sections[0]);
 
-    let workflows: HashMap<String, Workflow> = sections[0]
-        .lines()
-        .map(|l| l.parse::<Workflow>().unwrap())
-        .map(|wf| (wf.name.clone(), wf))
-        .collect();
-
-    let t: Target = Target::Workflow("in".to_owned());
-    let mut state = State::new();
-    let result = t.step(&mut state, &workflows);
-    result
-}
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct Workflow {
-    name: String,
-    rules: Vec<Rule>,
+    workflows
+        .volumes(START, all_ratings())
+        .get(ACCEPT)
+        .copied()
+        .unwrap_or(0)
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum Rule {
-    LessThan(char, usize, Target),
-    GreaterThan(char, usize, Target),
-    Target(Target),
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct State {
-    x: InclusionRange,
-    m: InclusionRange,
-    a: InclusionRange,
-    s: InclusionRange,
+/// Every rating from 1 to 4000 in each category
+fn all_ratings() -> Region {
+    Region::new(&["x", "m", "a", "s"], Range::new(1, 4001))
 }
 
-impl State {
-    fn new() -> Self {
-        let r = InclusionRange::new(1, 4001);
-        Self {
-            x: r.clone(),
-            m: r.clone(),
-            a: r.clone(),
-            s: r.clone(),
-        }
+fn parse_workflows(section: &str) -> Workflows {
+    let mut workflows = Workflows::new();
+    for wf in section.lines().map(|l| l.parse::<Workflow>().unwrap()) {
+        workflows.add(&wf.name, wf.rules);
     }
+    workflows
+}
 
-    fn length(&self) -> usize {
-        self.x.length() * self.m.length() * self.a.length() * self.s.length()
-    }
+/// Each workflow pointing at where its rules send parts, labelled with their conditions
+fn workflow_diagram(txt: &str) -> Diagram {
+    let sections: Vec<_> = input::empty_line_chunks(txt).collect();
+    let workflows = parse_workflows(sections[0]);
 
-    fn update_less_than(&mut self, c: char, less_than: usize) {
-        match c {
-            'x' => {
-                self.x.update_less_than(less_than);
-            }
-            'm' => {
-                self.m.update_less_than(less_than);
-            }
-            'a' => {
-                self.a.update_less_than(less_than);
-            }
-            's' => {
-                self.s.update_less_than(less_than);
-            }
-            _ => panic!("bad character"),
-        }
+    let mut d = Diagram::directed();
+    for (name, _) in workflows.iter() {
+        d.node(name, Shape::Box);
     }
-
-    fn update_more_than(&mut self, c: char, less_than: usize) {
-        match c {
-            'x' => {
-                self.x.update_more_than(less_than);
-            }
-            'm' => {
-                self.m.update_more_than(less_than);
-            }
-            'a' => {
-                self.a.update_more_than(less_than);
-            }
-            's' => {
-                self.s.update_more_than(less_than);
-            }
-            _ => panic!("bad character"),
+    for (name, rules) in workflows.iter() {
+        for rule in rules {
+            let label = match rule.condition {
+                Condition::Always => None,
+                _ => Some(rule.condition.to_string()),
+            };
+            d.edge(name, &rule.target, label.as_deref());
         }
     }
-}
-
-fn step(state: &mut State, workflow: &Workflow, workflows: &HashMap<String, Workflow>) -> usize {
-    let mut total = 0;
-    for r in &workflow.rules {
-        let contributes = match r {
-            Rule::LessThan(c, n, t) => {
-                let mut left = state.clone();
-
-                left.update_less_than(*c, *n);
-                state.update_more_than(*c, *n - 1);
-
-                t.step(&mut left, workflows)
-            }
-            Rule::GreaterThan(c, n, t) => {
-                let mut left = state.clone();
-
-                left.update_more_than(*c, *n);
-                state.update_less_than(*c, *n + 1);
-
-                t.step(&mut left, workflows)
-            }
-            Rule::Target(t) => t.step(state, workflows),
-        };
-        total += contributes;
-    }
-    total
+    d.node(START, Shape::Diamond)
+        .node(ACCEPT, Shape::DoubleCircle)
+        .node(REJECT, Shape::Circle);
+    d
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Target {
-    Workflow(String),
-    Accept,
-    Reject,
-}
-
-impl Target {
-    fn step(&self, state: &mut State, workflows: &HashMap<String, Workflow>) -> usize {
-        match self {
-            Target::Workflow(wf) => {
-                let next = workflows.get(wf).unwrap();
-                step(state, next, workflows)
-            }
-            Target::Accept => state.length(),
-            Target::Reject => 0,
-        }
-    }
+struct Workflow {
+    name: String,
+    rules: Vec<Rule>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Rating {
-    x: usize,
-    m: usize,
-    a: usize,
-    s: usize,
-}
+struct Rating(Record);
 
 impl Rating {
     fn total(&self) -> usize {
-        self.x + self.m + self.a + self.s
-    }
-
-    fn apply(&self, rule: &Rule) -> Option<Target> {
-        match rule {
-            Rule::LessThan('x', n, t) if self.x < *n => Some(t.clone()),
-            Rule::LessThan('m', n, t) if self.m < *n => Some(t.clone()),
-            Rule::LessThan('a', n, t) if self.a < *n => Some(t.clone()),
-            Rule::LessThan('s', n, t) if self.s < *n => Some(t.clone()),
-
-            Rule::GreaterThan('x', n, t) if self.x > *n => Some(t.clone()),
-            Rule::GreaterThan('m', n, t) if self.m > *n => Some(t.clone()),
-            Rule::GreaterThan('a', n, t) if self.a > *n => Some(t.clone()),
-            Rule::GreaterThan('s', n, t) if self.s > *n => Some(t.clone()),
-
-            Rule::Target(t) => Some(t.clone()),
-
-            _ => None,
-        }
+        self.0.values().sum()
     }
 }
 
@@ -216,89 +100,54 @@ impl FromStr for Workflow {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut outer = s.split("{");
-        let name = outer.next().unwrap().to_owned();
-        let rest = outer.next().unwrap().replace("}", "");
-        let rules: Vec<_> = rest
+        let (name, rest) = s.split_once("{").ok_or(format!("bad workflow {s}"))?;
+        let rules = rest
+            .trim_end_matches("}")
             .split(",")
-            .map(|rule| rule.parse::<Rule>().unwrap())
-            .collect();
+            .map(parse_rule)
+            .collect::<Result<_, _>>()?;
 
-        Ok(Workflow { name, rules })
+        Ok(Workflow {
+            name: name.to_owned(),
+            rules,
+        })
     }
 }
 
-impl FromStr for Rule {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn get_target(s: &str) -> Target {
-            match s {
-                "A" => Target::Accept,
-                "R" => Target::Reject,
-                other => Target::Workflow(other.to_owned()),
-            }
-        }
-
-        let parts: Vec<_> = s.split(":").collect();
-        if parts.len() == 1 {
-            Ok(Rule::Target(get_target(parts[0])))
-        } else {
-            let left = parts[0];
-
-            match left.split_once("<") {
-                Some((var, i)) => Ok(Rule::LessThan(
-                    var.chars().next().unwrap(),
-                    i.parse().unwrap(),
-                    get_target(parts[1]),
-                )),
-                None => Err("not less than".to_owned()),
-            }
-            .or(match left.split_once(">") {
-                Some((var, i)) => Ok(Rule::GreaterThan(
-                    var.chars().next().unwrap(),
-                    i.parse().unwrap(),
-                    get_target(parts[1]),
-                )),
-                None => Err("not less than or greater than".to_owned()),
-            })
-        }
-    }
+fn parse_rule(s: &str) -> Result<Rule, String> {
+    let Some((condition, target)) = s.split_once(":") else {
+        return Ok(Rule::always(s));
+    };
+    let parse = |(var, i): (&str, &str)| -> Result<(String, usize), String> {
+        let i = i.parse().map_err(|_| format!("bad rule {s}"))?;
+        Ok((var.to_owned(), i))
+    };
+    let condition = if let Some(less) = condition.split_once("<") {
+        let (var, i) = parse(less)?;
+        Condition::Less(var, i)
+    } else if let Some(more) = condition.split_once(">") {
+        let (var, i) = parse(more)?;
+        Condition::Greater(var, i)
+    } else {
+        return Err(format!("not less than or greater than {s}"));
+    };
+    Ok(Rule::new(condition, target))
 }
 
 impl FromStr for Rating {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cleaned = s.replace("{", "").replace("}", "");
-        let each_rating: Vec<_> = cleaned.split(",").collect();
-
-        let x = each_rating[0]
-            .split("=")
-            .nth(1)
-            .expect("=")
-            .parse::<usize>()
-            .expect("x value");
-        let m = each_rating[1]
-            .split("=")
-            .nth(1)
-            .expect("=")
-            .parse::<usize>()
-            .expect("x value");
-        let a = each_rating[2]
-            .split("=")
-            .nth(1)
-            .expect("=")
-            .parse::<usize>()
-            .expect("x value");
-        let s = each_rating[3]
-            .split("=")
-            .nth(1)
-            .expect("=")
-            .parse::<usize>()
-            .expect("x value");
-
-        Ok(Self { x, m, a, s })
+        s.trim_start_matches("{")
+            .trim_end_matches("}")
+            .split(",")
+            .map(|rating| {
+                let (category, value) = rating.split_once("=").ok_or(format!("bad rating {s}"))?;
+                let value = value.parse().map_err(|_| format!("bad rating {s}"))?;
+                Ok((category.to_owned(), value))
+            })
+            .collect::<Result<_, _>>()
+            .map(Rating)
     }
 }
 
@@ -317,16 +166,19 @@ mod tests {
         assert_eq!(167409079868000, part2(include_str!("input.test.txt")));
     }
 
+    #[test]
+    fn test_diagram() {
+        let d = workflow_diagram(include_str!("input.test.txt"));
+        assert_eq!(include_str!("input.test.dot"), d.to_dot());
+        assert_eq!(include_str!("input.test.mmd"), d.to_mermaid());
+    }
+
     #[test]
     fn test_parse_rule_less_than_workflow() {
         let str = "a<2006:qkq";
-        let rule = str.parse::<Rule>();
+        let rule = parse_rule(str);
         assert_eq!(
-            Ok(Rule::LessThan(
-                'a',
-                2006,
-                Target::Workflow("qkq".to_owned())
-            )),
+            Ok(Rule::new(Condition::Less("a".to_owned(), 2006), "qkq")),
             rule
         );
     }
@@ -334,15 +186,21 @@ mod tests {
     #[test]
     fn test_parse_rule_greater_than_reject() {
         let str = "a>1716:R";
-        let rule = str.parse::<Rule>();
-        assert_eq!(Ok(Rule::GreaterThan('a', 1716, Target::Reject)), rule);
+        let rule = parse_rule(str);
+        assert_eq!(
+            Ok(Rule::new(Condition::Greater("a".to_owned(), 1716), REJECT)),
+            rule
+        );
     }
 
     #[test]
     fn test_parse_rule_greater_than_accept() {
         let str = "m>1548:A";
-        let rule = str.parse::<Rule>();
-        assert_eq!(Ok(Rule::GreaterThan('m', 1548, Target::Accept)), rule);
+        let rule = parse_rule(str);
+        assert_eq!(
+            Ok(Rule::new(Condition::Greater("m".to_owned(), 1548), ACCEPT)),
+            rule
+        );
     }
 
     #[test]
@@ -352,9 +210,9 @@ mod tests {
         let expected = Ok(Workflow {
             name: "px".to_owned(),
             rules: vec![
-                Rule::LessThan('a', 2006, Target::Workflow("qkq".to_owned())),
-                Rule::GreaterThan('m', 2090, Target::Accept),
-                Rule::Target(Target::Workflow("rfg".to_owned())),
+                Rule::new(Condition::Less("a".to_owned(), 2006), "qkq"),
+                Rule::new(Condition::Greater("m".to_owned(), 2090), ACCEPT),
+                Rule::always("rfg"),
             ],
         });
 
@@ -368,9 +226,9 @@ mod tests {
         let expected = Ok(Workflow {
             name: "qqz".to_owned(),
             rules: vec![
-                Rule::GreaterThan('s', 2770, Target::Workflow("qs".to_owned())),
-                Rule::LessThan('m', 1801, Target::Workflow("hdj".to_owned())),
-                Rule::Target(Target::Reject),
+                Rule::new(Condition::Greater("s".to_owned(), 2770), "qs"),
+                Rule::new(Condition::Less("m".to_owned(), 1801), "hdj"),
+                Rule::always(REJECT),
             ],
         });
 
@@ -382,49 +240,45 @@ mod tests {
         let s = "{x=787,m=2655,a=1222,s=2876}";
         let parsed = s.parse::<Rating>();
 
-        assert_eq!(
-            Ok(Rating {
-                x: 787,
-                m: 2655,
-                a: 1222,
-                s: 2876
-            }),
-            parsed
-        )
+        let expected: Record = [("x", 787), ("m", 2655), ("a", 1222), ("s", 2876)]
+            .into_iter()
+            .map(|(c, v)| (c.to_owned(), v))
+            .collect();
+        assert_eq!(Ok(Rating(expected)), parsed)
     }
 
     #[test]
     fn test_initial_state_length() {
-        let s = State::new();
-        assert_eq!(4000 * 4000 * 4000 * 4000, s.length());
+        assert_eq!(4000 * 4000 * 4000 * 4000, all_ratings().volume());
     }
 
     #[test]
     fn test_start_point() {
-        let wf_in: Workflow = "in{A}".parse().unwrap();
-        let workflows: HashMap<_, _> = vec![("in".to_owned(), wf_in)].into_iter().collect();
-        let result = Target::Workflow("in".to_owned()).step(&mut State::new(), &workflows);
+        let result = part2("in{A}\n\n");
 
         assert_eq!(4000 * 4000 * 4000 * 4000, result);
     }
 
     #[test]
     fn test_updating_state_simple() {
-        let wf_in: Workflow = "in{s<21:A,R}".parse().unwrap();
-        let workflows: HashMap<_, _> = vec![("in".to_owned(), wf_in)].into_iter().collect();
-        let result = Target::Workflow("in".to_owned()).step(&mut State::new(), &workflows);
+        let result = part2("in{s<21:A,R}\n\n");
 
         assert_eq!(20 * 4000 * 4000 * 4000, result);
     }
 
     #[test]
     fn test_updating_state_simple_reject() {
-        let wf_in: Workflow = "in{s>20:R,A}".parse().unwrap();
-        let workflows: HashMap<_, _> = vec![("in".to_owned(), wf_in)].into_iter().collect();
-        let result = Target::Workflow("in".to_owned()).step(&mut State::new(), &workflows);
+        let result = part2("in{s>20:R,A}\n\n");
 
         assert!(result < (4000 * 4000 * 4000 * 4000));
         assert!(result > 0);
         assert_eq!(20 * 4000 * 4000 * 4000, result);
     }
+
+    #[test]
+    fn test_no_unreachable_rules() {
+        let sections: Vec<_> = input::empty_line_chunks(include_str!("input.test.txt")).collect();
+        let workflows = parse_workflows(sections[0]);
+        assert!(workflows.unreachable(START, all_ratings()).is_empty());
+    }
 }
@@ -1,14 +1,11 @@
-use std::{
-    collections::HashSet,
-    fmt::{Display, Write},
-    time::Instant,
-};
+use std::{collections::HashSet, time::Instant};
 
 use aoclib::{
+    automaton::{Automaton, Cells, Connectivity},
     cartesian::{Point, Transform},
-    grid::{Flip, FromChar, Grid, GridPosition},
+    grid::{Flip, FromChar, Grid, GridPosition, ToChar},
+    polynomial::DifferenceTable,
 };
-use itertools::Itertools;
 
 fn main() {
     let input = include_str!("input.txt");
@@ -20,8 +17,33 @@ fn main() {
 
 fn part1(txt: &str) -> usize {
     let g: Grid<_> = txt.parse().unwrap();
-    let map = Map(g.flip());
-    map.steps(64).len()
+    reachable(&g, 64)
+}
+
+/// The plots which can be reached in exactly `steps` steps without leaving the map
+fn reachable(g: &Grid<Position>, steps: usize) -> usize {
+    // rocks are `None`, plots are reached if a plot next to them was reached the step before
+    let plots = Grid {
+        rows: g
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|p| match p {
+                        Position::Rock => None,
+                        Position::Start => Some(true),
+                        Position::GardenPlot => Some(false),
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+    let rule = |plot: &Option<bool>, neighbours: &[&Option<bool>]| {
+        plot.map(|_| neighbours.contains(&&Some(true)))
+    };
+    let mut walk = Automaton::new(plots, Connectivity::Four, rule);
+    walk.run(steps);
+    walk.cells.population(|plot| *plot == Some(true)).unwrap()
 }
 
 fn part2(txt: &str) -> i64 {
@@ -34,47 +56,21 @@ fn part2(txt: &str) -> i64 {
     let s = map.start_pos().unwrap();
     let distance_to_side = s.x;
 
-    let samples: Vec<_> = (0..4).map(|i| grids_to_count(&map, i + 1)).collect();
-    let deltas: Vec<_> = samples
-        .iter()
-        .tuple_windows()
-        .map(|(left, right)| right - left)
-        .collect();
-    let second_deltas: Vec<_> = deltas
-        .iter()
-        .tuple_windows()
-        .map(|(left, right)| right - left)
-        .collect();
-
-    if second_deltas[0] != second_deltas[1] {
+    let samples: Vec<_> = (0..4).map(|i| grids_to_count(&map, i + 1) as i64).collect();
+    let growth = DifferenceTable::new(samples);
+
+    if growth.degree() != Some(2) {
         panic!("pattern non repeating at second level");
     }
 
-    let increment = second_deltas[0] as i64;
-
     if (target - distance_to_side) % map.0.width() as i64 != 0 {
         panic!("target was not an exact number of grids, interpolation logic won't work");
     }
 
-    let target_step = (target - distance_to_side) / map.0.width() as i64;
-
-    let mut step = samples.len() as i64;
-    let mut step_increment = *deltas.last().unwrap() as i64;
-    let mut value = *samples.last().unwrap() as i64;
-    loop {
-        step += 1;
-        step_increment += increment;
-        value += step_increment;
+    // samples are indexed from the first grid at 0
+    let target_grid = (target - distance_to_side) / map.0.width() as i64;
 
-        // println!("step {} = {}", step, value);
-
-        // have some off by ones somewhere
-        if step == target_step + 1 {
-            break;
-        }
-    }
-
-    value
+    growth.value_at(target_grid) as i64
 }
 
 #[allow(dead_code)]
@@ -156,36 +152,16 @@ impl Map {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromChar, ToChar)]
 enum Position {
+    #[tile('S')]
     Start,
+    #[tile('.')]
     GardenPlot,
+    #[tile('#')]
     Rock,
 }
 
-impl Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Position::Start => f.write_char('S'),
-            Position::GardenPlot => f.write_char('.'),
-            Position::Rock => f.write_char('#'),
-        }
-    }
-}
-
-impl FromChar for Position {
-    type Err = String;
-
-    fn from_char(c: char) -> Result<Self, Self::Err> {
-        match c {
-            'S' => Ok(Position::Start),
-            '.' => Ok(Position::GardenPlot),
-            '#' => Ok(Position::Rock),
-            other => Err(format!("bad char: {}", other)),
-        }
-    }
-}
-
 impl Flip for Position {
     fn flip(&self) -> Self {
         self.clone()
@@ -204,6 +180,23 @@ mod tests {
         assert_eq!(16, map.steps(6).len());
     }
 
+    #[test]
+    fn test_reachable_matches_steps() {
+        let txt = include_str!("input.test.txt");
+        let g: Grid<Position> = txt.parse().unwrap();
+        let map = Map(g.clone());
+
+        // the start is 5 from every edge, so the walk stays on the map until then
+        for steps in 0..=5 {
+            assert_eq!(
+                map.steps(steps).len(),
+                reachable(&g, steps),
+                "{steps} steps"
+            );
+        }
+        assert_eq!(16, reachable(&g, 6));
+    }
+
     #[test]
     fn test_part2_infinite_grid_example() {
         let origin = include_str!("input.test.txt");
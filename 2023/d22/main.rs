@@ -1,7 +1,6 @@
 use std::{cmp::Ordering, str::FromStr, time::Instant};
 
-use aoclib::range::Range;
-use itertools::Itertools;
+use aoclib::falling::{self, Cuboid, Settled};
 
 fn main() {
     let input = include_str!("input.txt");
@@ -12,112 +11,34 @@ fn main() {
 }
 
 fn part1(txt: &str) -> usize {
-    let mut bricks: Vec<Brick> = txt.lines().map(|l| l.parse().unwrap()).sorted().collect();
-    let collisions = drop_down(&mut bricks);
-
-    let mut count = 0;
-    for i in 0..bricks.len() {
-        let supports = &collisions.supports[i];
-
-        if supports.len() == 0 {
-            count += 1;
-        } else {
-            let can_remove = supports
-                .iter()
-                .all(|i_support| collisions.supports_me[*i_support].len() > 1);
-            if can_remove {
-                count += 1;
-            }
-        }
-    }
-
-    count
+    let settled = settle(txt);
+    (0..settled.cuboids.len())
+        .filter(|i| settled.is_removable(*i))
+        .count()
 }
 
 fn part2(txt: &str) -> usize {
-    0
+    let settled = settle(txt);
+    (0..settled.cuboids.len())
+        .map(|i| settled.falls_without(i).len())
+        .sum()
 }
 
-fn drop_down(bricks: &mut Vec<Brick>) -> Collisions {
-    let mut collisions = Collisions::new(bricks.len());
-    for i in 0..bricks.len() {
-        loop {
-            if bricks[i].z().from() == 1 {
-                break;
-            }
-
-            let moved_down = bricks[i].down();
-            let mut collided = false;
-            for (j, _) in bricks[0..i]
-                .iter()
-                .enumerate()
-                .filter(|(_, below)| moved_down.overlaps(below))
-            {
-                collided = true;
-                collisions.add_collision(i, j);
-            }
-
-            if collided {
-                break;
-            }
-            *&mut bricks[i] = moved_down;
-        }
-    }
-    // not sure if needed. I think bricks might be able to change order
-    // by sliding down the side without coliding
-    // bricks.sort();
-    collisions
+fn settle(txt: &str) -> Settled {
+    let cuboids: Vec<Cuboid> = txt
+        .lines()
+        .map(|l| l.parse::<Brick>().unwrap().cuboid())
+        .collect();
+    falling::settle(&cuboids, 1)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Brick([usize; 6]);
 
 impl Brick {
-    fn x(&self) -> Range {
-        return Range::new(self.0[0].min(self.0[3]), self.0[0].max(self.0[3]) + 1);
-    }
-
-    fn y(&self) -> Range {
-        return Range::new(self.0[1].min(self.0[4]), self.0[1].max(self.0[4]) + 1);
-    }
-
-    fn z(&self) -> Range {
-        return Range::new(self.0[2].min(self.0[5]), self.0[2].max(self.0[5]) + 1);
-    }
-
-    fn overlaps(&self, other: &Self) -> bool {
-        self.x().overlaps(other.x()) && self.y().overlaps(other.y()) && self.z().overlaps(other.z())
-    }
-
-    fn down(&self) -> Self {
-        Self([
-            self.0[0],
-            self.0[1],
-            self.0[2] - 1,
-            self.0[3],
-            self.0[4],
-            self.0[5] - 1,
-        ])
-    }
-}
-
-#[derive(Debug)]
-struct Collisions {
-    supports_me: Vec<Vec<usize>>,
-    supports: Vec<Vec<usize>>,
-}
-
-impl Collisions {
-    fn new(size: usize) -> Self {
-        Self {
-            supports_me: vec![vec![]; size],
-            supports: vec![vec![]; size],
-        }
-    }
-
-    fn add_collision(&mut self, brick: usize, onto: usize) {
-        self.supports_me[brick].push(onto);
-        self.supports[onto].push(brick);
+    fn cuboid(&self) -> Cuboid {
+        let [x1, y1, z1, x2, y2, z2] = self.0.map(|i| i as i64);
+        Cuboid::new((x1, y1, z1).into(), (x2, y2, z2).into())
     }
 }
 
@@ -129,7 +50,7 @@ impl PartialOrd for Brick {
 
 impl Ord for Brick {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.z().from().cmp(&other.z().from())
+        self.0[2].min(self.0[5]).cmp(&other.0[2].min(other.0[5]))
     }
 }
 
@@ -151,6 +72,8 @@ impl FromStr for Brick {
 #[cfg(test)]
 mod tests {
 
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -160,7 +83,7 @@ mod tests {
 
     #[test]
     fn test_example_p2() {
-        assert_eq!(0, part2(include_str!("input.test.txt")));
+        assert_eq!(7, part2(include_str!("input.test.txt")));
     }
 
     #[test]
@@ -178,35 +101,37 @@ mod tests {
 
     #[test]
     fn test_overlaps() {
-        let a = Brick([1, 0, 1, 1, 2, 1]);
-        let b = Brick([1, 1, 8, 1, 1, 9]);
+        let a = Brick([1, 0, 1, 1, 2, 1]).cuboid();
+        let b = Brick([1, 1, 8, 1, 1, 9]).cuboid();
 
-        assert_eq!(true, a.overlaps(&a));
-        assert_eq!(false, a.overlaps(&b));
+        assert!(a.overlaps(&a));
+        assert!(!a.overlaps(&b));
 
-        assert_eq!(false, a.down().overlaps(&a));
-        assert_eq!(true, a.down().overlaps(&a.down()));
+        assert!(!a.lowered(1).overlaps(&a));
+        assert!(a.lowered(1).overlaps(&a.lowered(1)));
     }
 
     #[test]
     fn test_drop_test_input() {
         let txt = include_str!("input.test.txt");
-        let mut parsed: Vec<Brick> = txt
-            .lines()
-            .map(|l| l.parse().unwrap())
-            .sorted_unstable()
-            .collect();
-        let col = drop_down(&mut parsed);
-
-        assert_eq!(1, parsed[0].z().from());
-        assert_eq!(2, parsed[1].z().from());
-        assert_eq!(2, parsed[2].z().from());
-        assert_eq!(3, parsed[3].z().from());
-        assert_eq!(3, parsed[4].z().from());
-        assert_eq!(4, parsed[5].z().from());
-        assert_eq!(5, parsed[6].z().from());
-        assert_eq!(7, parsed[6].z().to()); // + 1 of actual because of range bounds
-
-        println!("{:?}", col);
+        let settled = settle(txt);
+        let bottoms: Vec<i64> = settled.cuboids.iter().map(|c| c.min.z).collect();
+
+        assert_eq!(vec![1, 2, 2, 3, 3, 4, 5], bottoms);
+        assert_eq!(6, settled.cuboids[6].max.z);
+
+        // A holds up B and C, which both hold up D and E, which both hold up F, which holds up G
+        assert_eq!(
+            vec![
+                vec![1, 2],
+                vec![3, 4],
+                vec![3, 4],
+                vec![5],
+                vec![5],
+                vec![6],
+                vec![]
+            ],
+            settled.supports
+        );
     }
 }
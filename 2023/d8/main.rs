@@ -1,6 +1,6 @@
 use std::{collections::HashMap, time::Instant};
 
-use aoclib::{input, number};
+use aoclib::{input, number, scan};
 
 fn main() {
     let input = include_str!("input.txt");
@@ -17,12 +17,8 @@ fn part1(txt: &str) -> usize {
     let map: HashMap<_, _> = parts[1]
         .lines()
         .map(|l| {
-            let parts: Vec<_> = l.split(" = ").collect();
-            let key = parts[0];
-            let replaced = parts[1].replace("(", "");
-            let replaced = replaced.replace(")", "");
-            let parts: Vec<_> = replaced.split(", ").collect();
-            (key.to_owned(), (parts[0].to_owned(), parts[1].to_owned()))
+            let (key, left, right) = parse_node(l);
+            (key, (left, right))
         })
         .collect();
 
@@ -47,6 +43,10 @@ fn part1(txt: &str) -> usize {
     steps
 }
 
+fn parse_node(line: &str) -> (String, String, String) {
+    scan!("{} = ({}, {})", line, String, String, String).expect("valid node")
+}
+
 fn part2(txt: &str) -> u64 {
     let parts: Vec<_> = input::empty_line_chunks(txt).collect();
     let directions: Vec<_> = parts[0].chars().collect();
@@ -55,22 +55,18 @@ fn part2(txt: &str) -> u64 {
     let mut start_nodes: Vec<_> = Vec::new();
 
     for l in parts[1].lines() {
-        let parts: Vec<_> = l.split(" = ").collect();
-        let key = parts[0];
-        let replaced = parts[1].replace("(", "");
-        let replaced = replaced.replace(")", "");
-        let parts: Vec<_> = replaced.split(", ").collect();
-        map.insert(key.to_owned(), (parts[0].to_owned(), parts[1].to_owned()));
-        if key.chars().last().unwrap() == 'A' {
-            start_nodes.push(key);
+        let (key, left, right) = parse_node(l);
+        if key.ends_with('A') {
+            start_nodes.push(key.clone());
         }
+        map.insert(key, (left, right));
     }
 
     let mut steps: Vec<u64> = Vec::with_capacity(start_nodes.len());
 
     for i in 0..start_nodes.len() {
         let mut my_steps: u64 = 0;
-        let mut current_location = start_nodes[i];
+        let mut current_location = &start_nodes[i];
         loop {
             let d: u64 = my_steps % directions.len() as u64;
             let current_direction = directions[d as usize];
@@ -1,3 +1,4 @@
+use aoclib::polynomial::DifferenceTable;
 use itertools::Itertools;
 use std::{i64, str::FromStr, time::Instant};
 
@@ -28,75 +29,11 @@ struct ReportEntry(Vec<i64>);
 
 impl ReportEntry {
     fn descend(self) -> i64 {
-        let mut row = self.0;
-        let mut down: Vec<i64> = Vec::new();
-
-        down.push(*row.last().unwrap());
-
-        loop {
-            let row_itr = row.iter();
-            let row_offset_itr = row.iter().skip(1);
-            row = row_itr
-                .zip(row_offset_itr)
-                .map(|(&x, &y)| y - x)
-                .collect_vec();
-
-            down.push(*row.last().expect("sequence to finish before running out"));
-
-            // if row.iter().any(|&i| i < 0) {
-            //     panic!("{:?}", row);
-            // }
-
-            if row.iter().all(|&i| i == 0) {
-                break;
-            } else {
-                // println!("{:?}", row);
-            }
-        }
-
-        let mut increment = 0;
-        while let Some(next) = down.pop() {
-            increment += next;
-        }
-
-        increment
+        DifferenceTable::new(self.0).next_value()
     }
 
     fn descend_back(self) -> i64 {
-        let mut row = self.0;
-        let mut down: Vec<i64> = Vec::new();
-
-        down.push(*row.first().unwrap());
-
-        loop {
-            let row_itr = row.iter();
-            let row_offset_itr = row.iter().skip(1);
-            row = row_itr
-                .zip(row_offset_itr)
-                .map(|(&x, &y)| y - x)
-                .collect_vec();
-
-            down.push(*row.first().expect("sequence to finish before running out"));
-
-            // if row.iter().any(|&i| i < 0) {
-            //     panic!("{:?}", row);
-            // }
-
-            if row.iter().all(|&i| i == 0) {
-                break;
-            } else {
-                // println!("{:?}", row);
-            }
-        }
-
-        let mut increment = 0;
-        while let Some(next) = down.pop() {
-            // println!("{:?}", next);
-            increment = next - increment;
-            // println!("{:?}\n", increment);
-        }
-
-        increment
+        DifferenceTable::new(self.0).previous_value()
     }
 }
 
@@ -0,0 +1,283 @@
+//! Derive macros for `aoclib`, re-exported from `aoclib::grid` alongside the traits they implement.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Error, Fields, Ident, LitChar,
+    LitStr, Token,
+};
+
+/// Implements `aoclib::grid::FromChar` for an enum of unit variants,
+/// each marked with the chars it is parsed from, e.g. `#[tile('#')]` or `#[tile('.', 'S')]`.
+#[proc_macro_derive(FromChar, attributes(tile))]
+pub fn derive_from_char(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    variant_tiles(&input)
+        .map(|tiles| from_char(&input, &tiles))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `aoclib::grid::ToChar` and `Display` for an enum of unit variants,
+/// each written as the first char of its `#[tile(...)]` attribute.
+#[proc_macro_derive(ToChar, attributes(tile))]
+pub fn derive_to_char(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    variant_tiles(&input)
+        .map(|tiles| to_char(&input, &tiles))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Each variant along with the chars from its `#[tile(...)]` attribute
+fn variant_tiles(input: &DeriveInput) -> Result<Vec<(Ident, Vec<LitChar>)>, Error> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "tiles can only be derived for enums",
+        ));
+    };
+
+    let mut seen: Vec<char> = Vec::new();
+    let mut tiles = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "tile variants can't hold any data",
+            ));
+        }
+        let attr = variant
+            .attrs
+            .iter()
+            .find(|a| a.path().is_ident("tile"))
+            .ok_or_else(|| Error::new_spanned(variant, "missing a #[tile('c')] attribute"))?;
+        let chars: Vec<LitChar> = attr
+            .parse_args_with(Punctuated::<LitChar, Token![,]>::parse_terminated)?
+            .into_iter()
+            .collect();
+        if chars.is_empty() {
+            return Err(Error::new_spanned(attr, "a tile needs at least one char"));
+        }
+        for c in &chars {
+            if seen.contains(&c.value()) {
+                return Err(Error::new_spanned(
+                    c,
+                    "char is already used by another tile",
+                ));
+            }
+            seen.push(c.value());
+        }
+        tiles.push((variant.ident.clone(), chars));
+    }
+    Ok(tiles)
+}
+
+fn from_char(input: &DeriveInput, tiles: &[(Ident, Vec<LitChar>)]) -> TokenStream2 {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let arms = tiles.iter().map(|(variant, chars)| {
+        quote! { #(#chars)|* => Ok(#name::#variant), }
+    });
+
+    quote! {
+        impl #impl_generics ::aoclib::grid::FromChar for #name #ty_generics #where_clause {
+            type Err = String;
+
+            fn from_char(c: char) -> Result<Self, Self::Err> {
+                match c {
+                    #(#arms)*
+                    other => Err(format!("no {} tile for {:?}", stringify!(#name), other)),
+                }
+            }
+        }
+    }
+}
+
+fn to_char(input: &DeriveInput, tiles: &[(Ident, Vec<LitChar>)]) -> TokenStream2 {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let arms = tiles.iter().map(|(variant, chars)| {
+        let c = &chars[0];
+        quote! { #name::#variant => #c, }
+    });
+
+    quote! {
+        impl #impl_generics ::aoclib::grid::ToChar for #name #ty_generics #where_clause {
+            fn to_char(&self) -> char {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Write::write_char(f, ::aoclib::grid::ToChar::to_char(self))
+            }
+        }
+    }
+}
+
+/// Implements `FromStr` for a struct with named fields from an `#[input("...")]` pattern,
+/// where each `{field}` is parsed with the field's own `FromStr`.
+///
+/// A placeholder matches as little as it can unless given its own regex, as in `{id:\d+}`,
+/// everything else in the pattern must match exactly. Like `format!`, `{{` and `}}` are literal braces.
+#[proc_macro_derive(FromInput, attributes(input))]
+pub fn derive_from_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_input(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn from_input(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            name,
+            "FromInput can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(name, "FromInput needs named fields"));
+    };
+    let attr = input
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("input"))
+        .ok_or_else(|| Error::new_spanned(name, "missing an #[input(\"...\")] attribute"))?;
+    let pattern: LitStr = attr.parse_args()?;
+    let (regex, prefixes, placeholders) =
+        pattern_regex(&pattern.value()).map_err(|message| Error::new_spanned(&pattern, message))?;
+
+    let field_names: Vec<&Ident> = fields
+        .named
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .collect();
+    for placeholder in &placeholders {
+        if !field_names.iter().any(|f| f == &placeholder) {
+            return Err(Error::new_spanned(
+                &pattern,
+                format!("{name} has no field `{placeholder}`"),
+            ));
+        }
+    }
+    for field in &field_names {
+        if !placeholders.iter().any(|p| field == &p) {
+            return Err(Error::new_spanned(
+                field,
+                format!("`{field}` needs a {{{field}}} in the #[input] pattern"),
+            ));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let values = field_names.iter().map(|field| {
+        let key = field.to_string();
+        quote! { #field: ::aoclib::input::parse_field(&captures, #key)? }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::aoclib::input::ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                static PATTERN: ::std::sync::OnceLock<::aoclib::input::regex::Regex> =
+                    ::std::sync::OnceLock::new();
+                let captures = PATTERN
+                    .get_or_init(|| {
+                        ::aoclib::input::regex::Regex::new(#regex).expect("valid #[input] pattern")
+                    })
+                    .captures(s)
+                    .ok_or_else(|| ::aoclib::input::pattern_mismatch(s, #pattern, &[#(#prefixes),*]))?;
+                Ok(#name { #(#values),* })
+            }
+        }
+    })
+}
+
+/// An anchored regex with a named group for each `{field}` or `{field:regex}`,
+/// the regexes for each part of the pattern up to the end of a literal,
+/// and the field names in order. `{{` and `}}` are literal braces.
+fn pattern_regex(pattern: &str) -> Result<(String, Vec<String>, Vec<String>), String> {
+    let mut regex = String::from("^");
+    let mut literal = String::new();
+    let mut prefixes = Vec::new();
+    let mut names = Vec::new();
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push(c);
+            rest = &rest[2..];
+        } else if c == '{' {
+            let close = closing_brace(rest).ok_or_else(|| format!("unclosed {{ in {pattern:?}"))?;
+            let placeholder = &rest[1..close];
+            let (field, group) = placeholder.split_once(':').unwrap_or((placeholder, ".+?"));
+            if field.is_empty() {
+                return Err(format!("placeholders in {pattern:?} need a field name"));
+            }
+            if names.iter().any(|n| n == field) {
+                return Err(format!("`{field}` appears more than once in {pattern:?}"));
+            }
+            push_literal(&mut regex, &mut prefixes, &mut literal);
+            regex.push_str(&format!("(?P<{field}>{group})"));
+            names.push(field.to_owned());
+            rest = &rest[close + 1..];
+        } else if c == '}' {
+            return Err(format!(
+                "unmatched }} in {pattern:?}, use }}}} for a literal }}"
+            ));
+        } else {
+            literal.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    push_literal(&mut regex, &mut prefixes, &mut literal);
+    regex.push('$');
+    Ok((regex, prefixes, names))
+}
+
+fn push_literal(regex: &mut String, prefixes: &mut Vec<String>, literal: &mut String) {
+    if !literal.is_empty() {
+        regex.push_str(&escape(literal));
+        prefixes.push(regex.clone());
+        literal.clear();
+    }
+}
+
+/// Where the `}` closing the `{` that `s` starts with is,
+/// skipping over braces nested within it such as a regex's `{3}`
+fn closing_brace(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn escape(literal: &str) -> String {
+    let mut escaped = String::new();
+    for c in literal.chars() {
+        if "\\.+*?()|[]{}^$#&-~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+
+use crate::{
+    cartesian::Point,
+    cycle::{self, Cycle},
+    grid::{Grid, GridPosition},
+};
+
+/// Which surrounding cells count as a cell's neighbours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Up, left, right and down
+    Four,
+    /// Every touching cell, including diagonals
+    Eight,
+}
+
+impl Connectivity {
+    /// `(dx, dy)` of each neighbour in reading order, row by row from the top left,
+    /// where a larger `dy` is further down just as a larger row is in a [`Grid`].
+    pub fn offsets(&self) -> &'static [(i64, i64)] {
+        match self {
+            Connectivity::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// A generation of cells which a rule can turn into the next generation
+pub trait Cells: Clone + PartialEq {
+    type Cell;
+
+    /// The next generation, where each cell becomes `rule(cell, neighbours)`
+    /// with neighbours in the order of [`Connectivity::offsets`]
+    fn step<R>(&self, connectivity: Connectivity, rule: &R) -> Self
+    where
+        R: Fn(&Self::Cell, &[&Self::Cell]) -> Self::Cell;
+
+    /// How many cells are `alive`, or `None` if there are infinitely many
+    fn population<A>(&self, alive: A) -> Option<usize>
+    where
+        A: Fn(&Self::Cell) -> bool;
+}
+
+/// A bounded grid, cells on the edge have fewer neighbours
+impl<T> Cells for Grid<T>
+where
+    T: Clone + PartialEq,
+{
+    type Cell = T;
+
+    fn step<R>(&self, connectivity: Connectivity, rule: &R) -> Self
+    where
+        R: Fn(&T, &[&T]) -> T,
+    {
+        let (width, height) = (self.width() as i64, self.height() as i64);
+        let rows = (0..height)
+            .map(|row| {
+                (0..width)
+                    .map(|col| {
+                        let neighbours: Vec<&T> = connectivity
+                            .offsets()
+                            .iter()
+                            .map(|(dx, dy)| (col + dx, row + dy))
+                            .filter(|(x, y)| (0..width).contains(x) && (0..height).contains(y))
+                            .map(|(x, y)| self.at(&GridPosition::new(x as usize, y as usize)))
+                            .collect();
+                        rule(&self.rows[row as usize][col as usize], &neighbours)
+                    })
+                    .collect()
+            })
+            .collect();
+        Grid { rows }
+    }
+
+    fn population<A>(&self, alive: A) -> Option<usize>
+    where
+        A: Fn(&T) -> bool,
+    {
+        Some(self.rows.iter().flatten().filter(|c| alive(c)).count())
+    }
+}
+
+/// An unbounded grid where every cell not set holds the `background` value,
+/// so only the cells which differ from it are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    background: T,
+}
+
+impl<T> SparseGrid<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(background: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            background,
+        }
+    }
+
+    /// Each `grid` cell at `(col, row)`, so `y` grows downwards as rows do
+    pub fn from_grid(grid: &Grid<T>, background: T) -> Self {
+        let mut sparse = SparseGrid::new(background);
+        for (row, cells) in grid.rows.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                sparse.set(Point::new(col as i64, row as i64), cell.clone());
+            }
+        }
+        sparse
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn get(&self, p: &Point) -> &T {
+        self.cells.get(p).unwrap_or(&self.background)
+    }
+
+    pub fn set(&mut self, p: Point, value: T) {
+        if value == self.background {
+            self.cells.remove(&p);
+        } else {
+            self.cells.insert(p, value);
+        }
+    }
+
+    /// The cells which differ from the background
+    pub fn cells(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells.iter()
+    }
+
+    /// The smallest and largest corners of every cell which differs from the background
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let xs = self.cells.keys().map(|p| p.x);
+        let ys = self.cells.keys().map(|p| p.y);
+        Some((
+            Point::new(xs.clone().min()?, ys.clone().min()?),
+            Point::new(xs.max()?, ys.max()?),
+        ))
+    }
+}
+
+impl<T> Cells for SparseGrid<T>
+where
+    T: Clone + PartialEq,
+{
+    type Cell = T;
+
+    fn step<R>(&self, connectivity: Connectivity, rule: &R) -> Self
+    where
+        R: Fn(&T, &[&T]) -> T,
+    {
+        let offsets = connectivity.offsets();
+        let surrounded: Vec<&T> = offsets.iter().map(|_| &self.background).collect();
+        let mut next = SparseGrid::new(rule(&self.background, &surrounded));
+
+        // only cells within one of a non background cell can differ from the new background
+        if let Some((min, max)) = self.bounds() {
+            for y in min.y - 1..=max.y + 1 {
+                for x in min.x - 1..=max.x + 1 {
+                    let neighbours: Vec<&T> = offsets
+                        .iter()
+                        .map(|(dx, dy)| self.get(&Point::new(x + dx, y + dy)))
+                        .collect();
+                    let p = Point::new(x, y);
+                    let cell = rule(self.get(&p), &neighbours);
+                    next.set(p, cell);
+                }
+            }
+        }
+        next
+    }
+
+    fn population<A>(&self, alive: A) -> Option<usize>
+    where
+        A: Fn(&T) -> bool,
+    {
+        if alive(&self.background) {
+            None
+        } else {
+            Some(self.cells.values().filter(|c| alive(c)).count())
+        }
+    }
+}
+
+/// Runs generation after generation of `cells` under `rule`.
+///
+/// ```
+/// use aoclib::{automaton::{Automaton, Connectivity}, grid::Grid};
+///
+/// // Conway's game of life
+/// let life = |alive: &bool, neighbours: &[&bool]| {
+///     let n = neighbours.iter().filter(|n| ***n).count();
+///     n == 3 || (*alive && n == 2)
+/// };
+/// let blinker = Grid {
+///     rows: vec![vec![false, true, false]; 3],
+/// };
+/// let mut life = Automaton::new(blinker, Connectivity::Eight, life);
+/// assert_eq!(vec![Some(3), Some(3)], life.populations(2, |alive| *alive));
+/// assert_eq!(2, life.find_cycle().length);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Automaton<C, R> {
+    pub cells: C,
+    pub generation: usize,
+    connectivity: Connectivity,
+    rule: R,
+}
+
+impl<C, R> Automaton<C, R>
+where
+    C: Cells,
+    R: Fn(&C::Cell, &[&C::Cell]) -> C::Cell,
+{
+    pub fn new(cells: C, connectivity: Connectivity, rule: R) -> Self {
+        Automaton {
+            cells,
+            generation: 0,
+            connectivity,
+            rule,
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.cells = self.cells.step(self.connectivity, &self.rule);
+        self.generation += 1;
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Runs `generations` more generations, returning the population after each
+    pub fn populations<A>(&mut self, generations: usize, alive: A) -> Vec<Option<usize>>
+    where
+        A: Fn(&C::Cell) -> bool,
+    {
+        (0..generations)
+            .map(|_| {
+                self.step();
+                self.cells.population(&alive)
+            })
+            .collect()
+    }
+
+    /// Steps until a generation is the same as the one before it, giving up after `limit`.
+    /// Returns the generation which first stopped changing.
+    pub fn run_until_stable(&mut self, limit: usize) -> Option<usize> {
+        for _ in 0..limit {
+            let before = self.cells.clone();
+            self.step();
+            if before == self.cells {
+                return Some(self.generation - 1);
+            }
+        }
+        None
+    }
+
+    /// Where the generations from the current one start repeating,
+    /// without moving the automaton on.
+    /// Never returns if they never repeat.
+    pub fn find_cycle(&self) -> Cycle {
+        cycle::brent(
+            self.cells.clone(),
+            |c| c.step(self.connectivity, &self.rule),
+            |c| c.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(alive: &bool, neighbours: &[&bool]) -> bool {
+        let n = neighbours.iter().filter(|n| ***n).count();
+        n == 3 || (*alive && n == 2)
+    }
+
+    fn parse(txt: &str) -> Grid<bool> {
+        Grid {
+            rows: txt
+                .lines()
+                .map(|l| l.chars().map(|c| c == '#').collect())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_connectivity_reading_order() {
+        assert_eq!(4, Connectivity::Four.offsets().len());
+        let eight = Connectivity::Eight.offsets();
+        assert_eq!(8, eight.len());
+        let mut sorted = eight.to_vec();
+        sorted.sort_by_key(|(x, y)| (*y, *x));
+        assert_eq!(sorted, eight);
+    }
+
+    #[test]
+    fn test_grid_edges_have_fewer_neighbours() {
+        let g = Grid {
+            rows: vec![vec![0; 3]; 3],
+        };
+        let counted = g.step(Connectivity::Eight, &|_, n: &[&i32]| n.len() as i32);
+        assert_eq!(
+            vec![vec![3, 5, 3], vec![5, 8, 5], vec![3, 5, 3]],
+            counted.rows
+        );
+        let counted = g.step(Connectivity::Four, &|_, n: &[&i32]| n.len() as i32);
+        assert_eq!(
+            vec![vec![2, 3, 2], vec![3, 4, 3], vec![2, 3, 2]],
+            counted.rows
+        );
+    }
+
+    #[test]
+    fn test_still_life_is_stable() {
+        let block = parse("....\n.##.\n.##.\n....");
+        let mut a = Automaton::new(block.clone(), Connectivity::Eight, life);
+        assert_eq!(Some(0), a.run_until_stable(10));
+        assert_eq!(block, a.cells);
+    }
+
+    #[test]
+    fn test_settles_after_some_generations() {
+        // a line with nowhere to grow loses its ends, then its middle
+        let line = parse("###");
+        let mut a = Automaton::new(line, Connectivity::Eight, life);
+        assert_eq!(Some(2), a.run_until_stable(10));
+        assert_eq!(Some(0), a.cells.population(|c| *c));
+    }
+
+    #[test]
+    fn test_blinker_cycles() {
+        let blinker = parse(".....\n..#..\n..#..\n..#..\n.....");
+        let mut a = Automaton::new(blinker, Connectivity::Eight, life);
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 2
+            },
+            a.find_cycle()
+        );
+        assert_eq!(None, a.run_until_stable(10));
+        assert_eq!(10, a.generation);
+    }
+
+    #[test]
+    fn test_sparse_glider_travels() {
+        let glider = SparseGrid::from_grid(&parse(".#.\n..#\n###"), false);
+        let mut a = Automaton::new(glider.clone(), Connectivity::Eight, life);
+        assert_eq!(vec![Some(5); 4], a.populations(4, |c| *c));
+
+        // after 4 generations a glider is itself moved one down and right
+        let moved: Vec<Point> = glider
+            .cells()
+            .map(|(p, _)| Point::new(p.x + 1, p.y + 1))
+            .collect();
+        let mut expected = SparseGrid::new(false);
+        for p in moved {
+            expected.set(p, true);
+        }
+        assert_eq!(expected, a.cells);
+    }
+
+    #[test]
+    fn test_sparse_background_flips() {
+        // a lone cell with no live neighbours lights up, as in an image enhancement
+        // whose first bit is lit, so the infinite background flashes on and off
+        let flip = |alive: &bool, n: &[&bool]| match n.iter().filter(|n| ***n).count() {
+            0 => !alive,
+            8 => false,
+            _ => *alive,
+        };
+        let mut image = SparseGrid::new(false);
+        image.set(Point::new(0, 0), true);
+        let mut a = Automaton::new(image, Connectivity::Eight, flip);
+
+        // the lit ring left at the edge of the unlit patch, and the centre lit again
+        assert_eq!(vec![None, Some(17)], a.populations(2, |c| *c));
+        assert!(!a.cells.background());
+        assert_eq!(
+            Some((Point::new(-2, -2), Point::new(2, 2))),
+            a.cells.bounds()
+        );
+    }
+}
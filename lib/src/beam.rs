@@ -0,0 +1,333 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cartesian::Direction,
+    grid::{Grid, GridPosition},
+};
+
+/// The neighbour of `p` heading `direction`, where `Up` is towards row 0,
+/// or `None` if that is off the grid
+pub fn advance<T>(grid: &Grid<T>, p: &GridPosition, direction: Direction) -> Option<GridPosition> {
+    match direction {
+        Direction::Up if p.row > 0 => Some(p.up()),
+        Direction::Down if p.row + 1 < grid.height() => Some(p.down()),
+        Direction::Left if p.col > 0 => Some(p.left()),
+        Direction::Right if p.col + 1 < grid.width() => Some(p.right()),
+        _ => None,
+    }
+}
+
+/// Every position and direction a beam can enter the grid from outside,
+/// clockwise from the top left heading down
+pub fn edge_entries<T>(grid: &Grid<T>) -> Vec<(GridPosition, Direction)> {
+    let (width, height) = (grid.width(), grid.height());
+    let top = (0..width).map(|col| (GridPosition::new(col, 0), Direction::Down));
+    let right = (0..height).map(|row| (GridPosition::new(width - 1, row), Direction::Left));
+    let bottom = (0..width)
+        .rev()
+        .map(|col| (GridPosition::new(col, height - 1), Direction::Up));
+    let left = (0..height)
+        .rev()
+        .map(|row| (GridPosition::new(0, row), Direction::Right));
+    top.chain(right).chain(bottom).chain(left).collect()
+}
+
+/// Every position a beam entering `start` heading `direction` passes through.
+///
+/// `deflect` gives the directions a beam leaves a tile in given the direction it entered,
+/// none if the tile absorbs it.
+pub fn energise<T, F>(
+    grid: &Grid<T>,
+    start: GridPosition,
+    direction: Direction,
+    deflect: F,
+) -> HashSet<GridPosition>
+where
+    F: Fn(&T, Direction) -> Vec<Direction>,
+{
+    let mut seen = HashSet::new();
+    let mut beams = vec![(start, direction)];
+    while let Some((p, heading)) = beams.pop() {
+        if !seen.insert((p, heading)) {
+            continue;
+        }
+        for out in deflect(grid.at(&p), heading) {
+            if let Some(next) = advance(grid, &p, out) {
+                beams.push((next, out));
+            }
+        }
+    }
+    seen.into_iter().map(|(p, _)| p).collect()
+}
+
+/// Counts energised positions for many starting beams, sharing the work between them.
+///
+/// A beam is split into segments, each running straight from where it enters
+/// until the first tile which turns, splits or absorbs it. Segments which lead
+/// back to each other are grouped and the positions reachable from each group
+/// are only worked out once, so each further beam is mostly lookups.
+pub struct BeamTracer<'a, T, F> {
+    grid: &'a Grid<T>,
+    deflect: F,
+    segments: HashMap<(GridPosition, Direction), usize>,
+    cells: Vec<Vec<GridPosition>>,
+    exits: Vec<Vec<(GridPosition, Direction)>>,
+    group: Vec<Option<usize>>,
+    energised: Vec<Vec<u64>>,
+}
+
+impl<'a, T, F> BeamTracer<'a, T, F>
+where
+    F: Fn(&T, Direction) -> Vec<Direction>,
+{
+    pub fn new(grid: &'a Grid<T>, deflect: F) -> Self {
+        BeamTracer {
+            grid,
+            deflect,
+            segments: HashMap::new(),
+            cells: Vec::new(),
+            exits: Vec::new(),
+            group: Vec::new(),
+            energised: Vec::new(),
+        }
+    }
+
+    /// How many positions a beam entering `start` heading `direction` passes through
+    pub fn energised(&mut self, start: GridPosition, direction: Direction) -> usize {
+        let root = self.segment(start, direction);
+        if self.group[root].is_none() {
+            self.group_from(root);
+        }
+        let group = self.group[root].unwrap();
+        self.energised[group]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The most positions energised by any beam entering from the edge
+    pub fn max_energised(&mut self) -> usize {
+        edge_entries(self.grid)
+            .into_iter()
+            .map(|(p, d)| self.energised(p, d))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The segment starting at `start` heading `direction`, walking it the first time it is seen
+    fn segment(&mut self, start: GridPosition, direction: Direction) -> usize {
+        if let Some(id) = self.segments.get(&(start, direction)) {
+            return *id;
+        }
+        let mut cells = vec![start];
+        let mut p = start;
+        let exits = loop {
+            let out = (self.deflect)(self.grid.at(&p), direction);
+            if out != [direction] {
+                break out;
+            }
+            match advance(self.grid, &p, direction) {
+                Some(next) => {
+                    p = next;
+                    cells.push(p);
+                }
+                None => break vec![],
+            }
+        };
+        let exits = exits
+            .into_iter()
+            .filter_map(|out| advance(self.grid, &p, out).map(|next| (next, out)))
+            .collect();
+
+        let id = self.cells.len();
+        self.segments.insert((start, direction), id);
+        self.cells.push(cells);
+        self.exits.push(exits);
+        self.group.push(None);
+        id
+    }
+
+    /// Groups every segment reachable from `root` which isn't already grouped,
+    /// using Tarjan's strongly connected components algorithm so each group
+    /// is finished after every group it leads to.
+    fn group_from(&mut self, root: usize) {
+        // (order visited, lowest order reachable) of each segment in this search
+        let mut order: HashMap<usize, (usize, usize)> = HashMap::new();
+        let mut stack: Vec<usize> = Vec::new();
+        let mut on_stack: HashSet<usize> = HashSet::new();
+        let mut calls: Vec<(usize, usize)> = vec![(root, 0)];
+        order.insert(root, (0, 0));
+        stack.push(root);
+        on_stack.insert(root);
+
+        while let Some((v, exit)) = calls.last_mut() {
+            let v = *v;
+            if let Some(&(p, d)) = self.exits[v].get(*exit) {
+                *exit += 1;
+                let w = self.segment(p, d);
+                if self.group[w].is_some() {
+                    continue;
+                }
+                match order.get(&w) {
+                    None => {
+                        let n = order.len();
+                        order.insert(w, (n, n));
+                        stack.push(w);
+                        on_stack.insert(w);
+                        calls.push((w, 0));
+                    }
+                    Some(&(w_order, _)) if on_stack.contains(&w) => {
+                        let low = &mut order.get_mut(&v).unwrap().1;
+                        *low = (*low).min(w_order);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            let (v_order, v_low) = order[&v];
+            if let Some((u, _)) = calls.last() {
+                let low = &mut order.get_mut(u).unwrap().1;
+                *low = (*low).min(v_low);
+            }
+            if v_order == v_low {
+                let mut members = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack.remove(&w);
+                    members.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                self.finish_group(&members);
+            }
+        }
+    }
+
+    fn finish_group(&mut self, members: &[usize]) {
+        let width = self.grid.width();
+        let mut energised = vec![0u64; (width * self.grid.height()).div_ceil(64)];
+        for m in members {
+            for p in &self.cells[*m] {
+                let i = p.row * width + p.col;
+                energised[i / 64] |= 1 << (i % 64);
+            }
+            for key in &self.exits[*m] {
+                // every exit is either a member or already in a finished group
+                if let Some(g) = self.group[self.segments[key]] {
+                    for (word, other) in energised.iter_mut().zip(&self.energised[g]) {
+                        *word |= other;
+                    }
+                }
+            }
+        }
+        let id = self.energised.len();
+        self.energised.push(energised);
+        for m in members {
+            self.group[*m] = Some(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRAPTION: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    fn deflect(tile: &char, heading: Direction) -> Vec<Direction> {
+        use Direction::*;
+        match (tile, heading) {
+            ('/', Right) => vec![Up],
+            ('/', Left) => vec![Down],
+            ('/', Up) => vec![Right],
+            ('/', Down) => vec![Left],
+            ('\\', Right) => vec![Down],
+            ('\\', Left) => vec![Up],
+            ('\\', Up) => vec![Left],
+            ('\\', Down) => vec![Right],
+            ('|', Left | Right) => vec![Up, Down],
+            ('-', Up | Down) => vec![Left, Right],
+            _ => vec![heading],
+        }
+    }
+
+    fn grid(txt: &str) -> Grid<char> {
+        txt.parse().unwrap()
+    }
+
+    #[test]
+    fn test_energise() {
+        let g = grid(CONTRAPTION);
+        let energised = energise(&g, GridPosition::new(0, 0), Direction::Right, deflect);
+        assert_eq!(46, energised.len());
+        assert!(energised.contains(&GridPosition::new(1, 9)));
+        assert!(!energised.contains(&GridPosition::new(0, 9)));
+    }
+
+    #[test]
+    fn test_tracer_matches_energise() {
+        let g = grid(CONTRAPTION);
+        let mut tracer = BeamTracer::new(&g, deflect);
+        for (p, d) in edge_entries(&g) {
+            assert_eq!(
+                energise(&g, p, d, deflect).len(),
+                tracer.energised(p, d),
+                "entering {p:?} heading {d}"
+            );
+        }
+        assert_eq!(51, tracer.max_energised());
+    }
+
+    #[test]
+    fn test_loops() {
+        // a beam split back into a loop of mirrors
+        let g = grid("/.-.\\\n.....\n\\.../");
+        assert_eq!(
+            13,
+            BeamTracer::new(&g, deflect).energised(GridPosition::new(2, 2), Direction::Up)
+        );
+        assert_eq!(
+            13,
+            energise(&g, GridPosition::new(2, 2), Direction::Up, deflect).len()
+        );
+    }
+
+    #[test]
+    fn test_absorbed() {
+        let g = grid("..#..");
+        let absorb = |tile: &char, heading| match tile {
+            '#' => vec![],
+            _ => vec![heading],
+        };
+        let mut tracer = BeamTracer::new(&g, absorb);
+        assert_eq!(
+            3,
+            tracer.energised(GridPosition::new(0, 0), Direction::Right)
+        );
+        assert_eq!(
+            2,
+            tracer.energised(GridPosition::new(3, 0), Direction::Right)
+        );
+    }
+
+    #[test]
+    fn test_edge_entries() {
+        let g = grid("..\n..\n..");
+        let entries = edge_entries(&g);
+        assert_eq!(2 * (2 + 3), entries.len());
+        assert_eq!((GridPosition::new(0, 0), Direction::Down), entries[0]);
+        assert_eq!((GridPosition::new(1, 0), Direction::Left), entries[2]);
+        assert_eq!((GridPosition::new(0, 0), Direction::Right), entries[9]);
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::number::{self, Rational};
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Point {
     pub x: i64,
@@ -23,7 +25,7 @@ impl std::ops::Mul<i64> for Transform {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
     Left,
     Right,
@@ -91,13 +93,16 @@ impl Point {
     }
 
     pub fn on(&self, v: &Vector) -> bool {
-        let min_x = v.start.x.min(v.end.x);
-        let max_x = v.start.x.max(v.end.x);
-        let min_y = v.start.y.min(v.end.y);
-        let max_y = v.start.y.max(v.end.y);
-        let on_x = min_x <= self.x && max_x >= self.x;
-        let on_y = min_y <= self.y && max_y >= self.y;
-        on_y && on_x
+        self.on_segment(&v.start, &v.end)
+    }
+
+    /// Exactly on the straight line segment between `a` and `b` inclusive
+    pub fn on_segment(&self, a: &Point, b: &Point) -> bool {
+        cross(a, b, self) == 0
+            && a.x.min(b.x) <= self.x
+            && self.x <= a.x.max(b.x)
+            && a.y.min(b.y) <= self.y
+            && self.y <= a.y.max(b.y)
     }
 
     pub fn to(self, end: Point) -> Vector {
@@ -105,6 +110,14 @@ impl Point {
     }
 }
 
+/// The cross product of `a -> b` with `a -> p`, positive when `p` is to the left of `a -> b`.
+///
+/// Worked out as `i128` so points far apart can't overflow.
+fn cross(a: &Point, b: &Point, p: &Point) -> i128 {
+    let (ax, ay) = (a.x as i128, a.y as i128);
+    (b.x as i128 - ax) * (p.y as i128 - ay) - (b.y as i128 - ay) * (p.x as i128 - ax)
+}
+
 impl Transform {
     pub fn left() -> Self {
         (-1, 0).into()
@@ -139,7 +152,7 @@ impl Transform {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Vector {
     pub start: Point,
     pub end: Point,
@@ -149,6 +162,214 @@ impl Vector {
     pub fn length(&self) -> u64 {
         self.start.x.abs_diff(self.end.x) + self.start.y.abs_diff(self.end.y)
     }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        p.on_segment(&self.start, &self.end)
+    }
+
+    /// The points to draw this vector on a grid using
+    /// [Bresenham's line algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm).
+    ///
+    /// Works for any slope, for horizontal, vertical and 45° lines
+    /// this is exactly every point on the vector.
+    pub fn points(&self) -> Rasterise {
+        Rasterise {
+            current: Some(self.start.clone()),
+            end: self.end.clone(),
+            dx: (self.end.x - self.start.x).abs(),
+            dy: -(self.end.y - self.start.y).abs(),
+            step_x: (self.end.x - self.start.x).signum(),
+            step_y: (self.end.y - self.start.y).signum(),
+            err: (self.end.x - self.start.x).abs() - (self.end.y - self.start.y).abs(),
+        }
+    }
+
+    /// Only the integer points lying exactly on the vector, from start to end
+    pub fn lattice_points(&self) -> impl Iterator<Item = Point> + '_ {
+        let dx = self.end.x - self.start.x;
+        let dy = self.end.y - self.start.y;
+        let steps = number::gcd(dx.abs(), dy.abs());
+        let step: Transform = if steps == 0 {
+            (0, 0).into()
+        } else {
+            (dx / steps, dy / steps).into()
+        };
+        (0..=steps).map(move |i| self.start.transform(&(step.clone() * i)))
+    }
+
+    /// Where this vector meets another, if at all.
+    ///
+    /// Crossing vectors meet at a single point which may not be an integer point.
+    /// Collinear vectors can share a whole section, which always starts and
+    /// ends on one of the vector's own end points.
+    pub fn intersection(&self, other: &Vector) -> Option<Intersection> {
+        let r = (self.end.x - self.start.x, self.end.y - self.start.y);
+        let s = (other.end.x - other.start.x, other.end.y - other.start.y);
+        let qp = (other.start.x - self.start.x, other.start.y - self.start.y);
+        let cross =
+            |a: (i64, i64), b: (i64, i64)| a.0 as i128 * b.1 as i128 - a.1 as i128 * b.0 as i128;
+
+        let r_cross_s = cross(r, s);
+        if r_cross_s == 0 {
+            if cross(qp, r) != 0 || cross(qp, s) != 0 {
+                // parallel and never touching
+                return None;
+            }
+            let mut shared: Vec<Point> = [&self.start, &self.end, &other.start, &other.end]
+                .into_iter()
+                .filter(|p| self.contains(p) && other.contains(p))
+                .cloned()
+                .collect();
+            shared.sort();
+            return match (shared.first(), shared.last()) {
+                (Some(a), Some(b)) if a == b => Some(Intersection::Point(
+                    (a.x as i128).into(),
+                    (a.y as i128).into(),
+                )),
+                (Some(a), Some(b)) => Some(Intersection::Overlap(a.clone().to(b.clone()))),
+                _ => None,
+            };
+        }
+
+        let t = Rational::new(cross(qp, s), r_cross_s);
+        let u = Rational::new(cross(qp, r), r_cross_s);
+        let zero = Rational::from(0);
+        let one = Rational::from(1);
+        if t < zero || t > one || u < zero || u > one {
+            return None;
+        }
+        Some(Intersection::Point(
+            Rational::from(self.start.x) + t * Rational::from(r.0),
+            Rational::from(self.start.y) + t * Rational::from(r.1),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intersection {
+    Point(Rational, Rational),
+    Overlap(Vector),
+}
+
+/// Iterator over the points of a [`Vector`], see [`Vector::points`]
+#[derive(Debug, Clone)]
+pub struct Rasterise {
+    current: Option<Point>,
+    end: Point,
+    dx: i64,
+    dy: i64,
+    step_x: i64,
+    step_y: i64,
+    err: i64,
+}
+
+impl Iterator for Rasterise {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.take()?;
+        if current != self.end {
+            let mut next = current.clone();
+            let e2 = 2 * self.err;
+            if e2 >= self.dy {
+                self.err += self.dy;
+                next.x += self.step_x;
+            }
+            if e2 <= self.dx {
+                self.err += self.dx;
+                next.y += self.step_y;
+            }
+            self.current = Some(next);
+        }
+        Some(current)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from(value: (i64, i64, i64)) -> Self {
+        Point3::new(value.0, value.1, value.2)
+    }
+}
+
+/// An infinite line through `origin` heading in `direction`,
+/// such as a hailstone with a position and velocity.
+///
+/// For lines in 2D use a `z` of `0` for both.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Line3 {
+    pub origin: Point3,
+    pub direction: Point3,
+}
+
+/// Where two [`Line3`]s meet. `t` and `s` are how far along each line's
+/// direction the meeting point is, negative is behind the origin,
+/// or in the past for hailstones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIntersection {
+    pub t: Rational,
+    pub s: Rational,
+    pub point: [Rational; 3],
+}
+
+impl Line3 {
+    pub fn new(origin: Point3, direction: Point3) -> Self {
+        Line3 { origin, direction }
+    }
+
+    pub fn at(&self, t: Rational) -> [Rational; 3] {
+        [
+            Rational::from(self.origin.x) + t * Rational::from(self.direction.x),
+            Rational::from(self.origin.y) + t * Rational::from(self.direction.y),
+            Rational::from(self.origin.z) + t * Rational::from(self.direction.z),
+        ]
+    }
+
+    /// The single point where both lines meet.
+    /// `None` for parallel (including identical) or skew lines.
+    pub fn intersection(&self, other: &Line3) -> Option<LineIntersection> {
+        let as_i128 = |p: &Point3| [p.x as i128, p.y as i128, p.z as i128];
+        let cross = |a: [i128; 3], b: [i128; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let dot = |a: [i128; 3], b: [i128; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+
+        let d1 = as_i128(&self.direction);
+        let d2 = as_i128(&other.direction);
+        let o1 = as_i128(&self.origin);
+        let o2 = as_i128(&other.origin);
+        let w = [o2[0] - o1[0], o2[1] - o1[1], o2[2] - o1[2]];
+
+        let n = cross(d1, d2);
+        let n_squared = dot(n, n);
+        if n_squared == 0 || dot(w, n) != 0 {
+            return None;
+        }
+
+        let t = Rational::new(dot(cross(w, d2), n), n_squared);
+        let s = Rational::new(dot(cross(w, d1), n), n_squared);
+        Some(LineIntersection {
+            t,
+            s,
+            point: self.at(t),
+        })
+    }
 }
 
 impl From<(Point, Point)> for Vector {
@@ -191,6 +412,123 @@ impl Plane {
     }
 }
 
+/// A simple (non self-intersecting) polygon described by its vertices in order.
+///
+/// The polygon is implicitly closed, the last vertex connects back to the first.
+/// Repeating the first vertex at the end is allowed and makes no difference.
+///
+/// ```
+/// use aoclib::cartesian::{Point, Polygon};
+///
+/// let square = Polygon(vec![
+///     Point::new(0, 0),
+///     Point::new(2, 0),
+///     Point::new(2, 2),
+///     Point::new(0, 2),
+/// ]);
+/// assert_eq!(4.0, square.area());
+/// assert_eq!(8, square.boundary_points());
+/// assert_eq!(1, square.interior_points());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon(pub Vec<Point>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+impl Polygon {
+    fn edges(&self) -> impl Iterator<Item = (&Point, &Point)> {
+        self.0.iter().zip(self.0.iter().cycle().skip(1))
+    }
+
+    /// Twice the signed area via the shoelace formula.
+    /// Positive when the vertices run anti-clockwise.
+    ///
+    /// Kept doubled so it stays an exact integer,
+    /// and worked out as `i128` like [`Polygon::locate`] so large polygons can't overflow.
+    pub fn double_signed_area(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128)
+            .sum()
+    }
+
+    pub fn signed_area(&self) -> f64 {
+        self.double_signed_area() as f64 / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Euclidean length of all the edges
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| {
+                let dx = (b.x - a.x) as f64;
+                let dy = (b.y - a.y) as f64;
+                (dx * dx + dy * dy).sqrt()
+            })
+            .sum()
+    }
+
+    /// The number of integer points lying on the edges of the polygon
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| number::gcd((b.x - a.x).abs(), (b.y - a.y).abs()))
+            .sum()
+    }
+
+    /// The number of integer points strictly inside the polygon.
+    ///
+    /// Uses [Pick's theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem)
+    /// `A = i + b/2 - 1` rearranged for `i`.
+    /// A polygon without any area, such as one with fewer than 3 vertices, has no inside.
+    pub fn interior_points(&self) -> i64 {
+        let double_area = self.double_signed_area().abs();
+        if self.0.len() < 3 || double_area == 0 {
+            return 0;
+        }
+        let interior = (double_area - self.boundary_points() as i128 + 2) / 2;
+        i64::try_from(interior).expect("more interior points than fit in an i64")
+    }
+
+    /// All integer points covered by the polygon, both interior and boundary.
+    ///
+    /// This is the "area" when each point is a tile, for example
+    /// a trench dug around a lagoon.
+    pub fn enclosed_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn locate(&self, p: &Point) -> PointLocation {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if p.on_segment(a, b) {
+                return PointLocation::Boundary;
+            }
+            // only edges straddling the horizontal ray from p count,
+            // treating an edge as half open so shared vertices count once
+            if (a.y > p.y) != (b.y > p.y) && (cross(a, b, p) > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+
+    /// Is the point inside or on the boundary of this polygon
+    pub fn contains(&self, p: &Point) -> bool {
+        self.locate(p) != PointLocation::Outside
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -256,4 +594,272 @@ mod tests {
 
         assert_eq!(6, vector.length());
     }
+
+    fn rectangle() -> Polygon {
+        // ###
+        // ###
+        Polygon(vec![
+            (0, 0).into(),
+            (2, 0).into(),
+            (2, 1).into(),
+            (0, 1).into(),
+        ])
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        let square = Polygon(vec![
+            (0, 0).into(),
+            (4, 0).into(),
+            (4, 4).into(),
+            (0, 4).into(),
+        ]);
+        assert_eq!(32, square.double_signed_area());
+        assert_eq!(16.0, square.area());
+        assert_eq!(16.0, square.perimeter());
+
+        let mut clockwise = square.0.clone();
+        clockwise.reverse();
+        assert_eq!(-16.0, Polygon(clockwise).signed_area());
+
+        let triangle = Polygon(vec![(0, 0).into(), (3, 0).into(), (0, 1).into()]);
+        assert_eq!(1.5, triangle.area());
+    }
+
+    #[test]
+    fn test_polygon_area_does_not_overflow() {
+        // far enough out that each shoelace term alone is beyond an i64
+        let far = 1 << 40;
+        let square = Polygon(vec![
+            (far, far).into(),
+            (far + 4, far).into(),
+            (far + 4, far + 4).into(),
+            (far, far + 4).into(),
+        ]);
+        assert_eq!(32, square.double_signed_area());
+        assert_eq!(9, square.interior_points());
+        assert_eq!(
+            PointLocation::Inside,
+            square.locate(&(far + 2, far + 2).into())
+        );
+    }
+
+    #[test]
+    fn test_polygon_closed_vertices() {
+        let open = rectangle();
+        let mut closed = open.0.clone();
+        closed.push(closed[0].clone());
+        let closed = Polygon(closed);
+
+        assert_eq!(open.double_signed_area(), closed.double_signed_area());
+        assert_eq!(open.boundary_points(), closed.boundary_points());
+    }
+
+    #[test]
+    fn test_polygon_picks() {
+        let square = Polygon(vec![
+            (0, 0).into(),
+            (4, 0).into(),
+            (4, 4).into(),
+            (0, 4).into(),
+        ]);
+        assert_eq!(16, square.boundary_points());
+        assert_eq!(9, square.interior_points());
+        assert_eq!(25, square.enclosed_points());
+
+        // diagonal edges only pass through some lattice points
+        let diamond = Polygon(vec![
+            (2, 0).into(),
+            (4, 2).into(),
+            (2, 4).into(),
+            (0, 2).into(),
+        ]);
+        assert_eq!(8, diamond.boundary_points());
+        assert_eq!(5, diamond.interior_points());
+
+        assert_eq!(6, rectangle().enclosed_points());
+        assert_eq!(0, rectangle().interior_points());
+    }
+
+    #[test]
+    fn test_polygon_degenerate_has_no_interior() {
+        assert_eq!(0, Polygon(vec![]).interior_points());
+        assert_eq!(0, Polygon(vec![(1, 1).into()]).interior_points());
+        assert_eq!(
+            0,
+            Polygon(vec![(0, 0).into(), (4, 0).into()]).interior_points()
+        );
+        let flat = Polygon(vec![(0, 0).into(), (2, 2).into(), (4, 4).into()]);
+        assert_eq!(0, flat.interior_points());
+    }
+
+    #[test]
+    fn test_polygon_locate() {
+        //  ....#
+        //  ...##
+        //  ..###
+        //  .####
+        //  #####
+        let triangle = Polygon(vec![(0, 0).into(), (4, 0).into(), (4, 4).into()]);
+
+        assert_eq!(PointLocation::Inside, triangle.locate(&(3, 1).into()));
+        assert_eq!(PointLocation::Boundary, triangle.locate(&(2, 2).into()));
+        assert_eq!(PointLocation::Boundary, triangle.locate(&(4, 0).into()));
+        assert_eq!(PointLocation::Boundary, triangle.locate(&(4, 3).into()));
+        assert_eq!(PointLocation::Outside, triangle.locate(&(1, 2).into()));
+        assert_eq!(PointLocation::Outside, triangle.locate(&(5, 0).into()));
+        // ray passes straight through the (4, 4) vertex
+        assert_eq!(PointLocation::Outside, triangle.locate(&(-1, 4).into()));
+        assert_eq!(PointLocation::Outside, triangle.locate(&(-1, 0).into()));
+    }
+
+    #[test]
+    fn test_polygon_locate_concave() {
+        // ##...##
+        // ##...##
+        // ##...##
+        // #######
+        // #######
+        let u = Polygon(vec![
+            (0, 0).into(),
+            (6, 0).into(),
+            (6, 4).into(),
+            (4, 4).into(),
+            (4, 2).into(),
+            (2, 2).into(),
+            (2, 4).into(),
+            (0, 4).into(),
+        ]);
+        assert_eq!(PointLocation::Inside, u.locate(&(1, 3).into()));
+        assert_eq!(PointLocation::Inside, u.locate(&(3, 1).into()));
+        assert_eq!(PointLocation::Boundary, u.locate(&(3, 2).into()));
+        assert_eq!(PointLocation::Outside, u.locate(&(3, 3).into()));
+        assert!(!u.contains(&(3, 4).into()));
+        assert_eq!(9, u.interior_points());
+        assert_eq!(33, u.enclosed_points());
+    }
+
+    #[test]
+    fn test_point_on_segment() {
+        let a: Point = (0, 0).into();
+        let b: Point = (4, 2).into();
+        assert!(Point::new(2, 1).on_segment(&a, &b));
+        assert!(!Point::new(1, 1).on_segment(&a, &b));
+        assert!(!Point::new(6, 3).on_segment(&a, &b));
+    }
+    #[test]
+    fn test_point_not_on_diagonal_neighbour() {
+        let v: Vector = ((0, 0).into(), (4, 2).into()).into();
+        assert!(Point::new(2, 1).on(&v));
+        assert!(!Point::new(1, 1).on(&v));
+        assert!(!Point::new(3, 1).on(&v));
+    }
+
+    #[test]
+    fn test_vector_points() {
+        let horizontal: Vector = ((3, 1).into(), (1, 1).into()).into();
+        let expected: Vec<Point> = vec![(3, 1).into(), (2, 1).into(), (1, 1).into()];
+        assert_eq!(expected, horizontal.points().collect::<Vec<_>>());
+
+        let diagonal: Vector = ((9, 7).into(), (7, 9).into()).into();
+        let expected: Vec<Point> = vec![(9, 7).into(), (8, 8).into(), (7, 9).into()];
+        assert_eq!(expected, diagonal.points().collect::<Vec<_>>());
+
+        // ..###
+        // ##...
+        let shallow: Vector = ((0, 0).into(), (4, 1).into()).into();
+        let expected: Vec<Point> = vec![
+            (0, 0).into(),
+            (1, 0).into(),
+            (2, 1).into(),
+            (3, 1).into(),
+            (4, 1).into(),
+        ];
+        assert_eq!(expected, shallow.points().collect::<Vec<_>>());
+
+        let single: Vector = ((2, 2).into(), (2, 2).into()).into();
+        assert_eq!(vec![Point::new(2, 2)], single.points().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_vector_lattice_points() {
+        let v: Vector = ((0, 0).into(), (6, 3).into()).into();
+        let expected: Vec<Point> = vec![(0, 0).into(), (2, 1).into(), (4, 2).into(), (6, 3).into()];
+        assert_eq!(expected, v.lattice_points().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_vector_intersection_crossing() {
+        let a: Vector = ((0, 0).into(), (4, 4).into()).into();
+        let b: Vector = ((0, 4).into(), (4, 0).into()).into();
+        assert_eq!(
+            Some(Intersection::Point(2_i64.into(), 2_i64.into())),
+            a.intersection(&b)
+        );
+
+        let c: Vector = ((0, 1).into(), (1, 0).into()).into();
+        let d: Vector = ((0, 0).into(), (1, 1).into()).into();
+        assert_eq!(
+            Some(Intersection::Point(
+                Rational::new(1, 2),
+                Rational::new(1, 2)
+            )),
+            c.intersection(&d)
+        );
+
+        let short: Vector = ((0, 4).into(), (1, 3).into()).into();
+        assert_eq!(None, a.intersection(&short));
+    }
+
+    #[test]
+    fn test_vector_intersection_collinear() {
+        let a: Vector = ((0, 0).into(), (4, 0).into()).into();
+        let b: Vector = ((6, 0).into(), (2, 0).into()).into();
+        assert_eq!(
+            Some(Intersection::Overlap(((2, 0).into(), (4, 0).into()).into())),
+            a.intersection(&b)
+        );
+
+        let touching: Vector = ((4, 0).into(), (5, 0).into()).into();
+        assert_eq!(
+            Some(Intersection::Point(4_i64.into(), 0_i64.into())),
+            a.intersection(&touching)
+        );
+
+        let parallel: Vector = ((0, 1).into(), (4, 1).into()).into();
+        assert_eq!(None, a.intersection(&parallel));
+
+        let apart: Vector = ((5, 0).into(), (7, 0).into()).into();
+        assert_eq!(None, a.intersection(&apart));
+    }
+
+    #[test]
+    fn test_line3_intersection() {
+        let a = Line3::new((0, 0, 0).into(), (1, 1, 1).into());
+        let b = Line3::new((4, 0, 2).into(), (-1, 1, 0).into());
+        let hit = a.intersection(&b).unwrap();
+        assert_eq!(Rational::from(2), hit.t);
+        assert_eq!(Rational::from(2), hit.s);
+        assert_eq!([Rational::from(2); 3], hit.point);
+
+        let skew = Line3::new((0, 0, 1).into(), (1, -1, 0).into());
+        assert_eq!(None, a.intersection(&skew));
+
+        let parallel = Line3::new((1, 0, 0).into(), (2, 2, 2).into());
+        assert_eq!(None, a.intersection(&parallel));
+    }
+
+    #[test]
+    fn test_line3_hailstones_in_2d() {
+        // Hailstone A: 19, 13, 30 @ -2, 1, -2
+        // Hailstone B: 18, 19, 22 @ -1, -1, -2
+        // paths cross inside the test area (at x=14.333, y=15.333).
+        let a = Line3::new((19, 13, 0).into(), (-2, 1, 0).into());
+        let b = Line3::new((18, 19, 0).into(), (-1, -1, 0).into());
+        let hit = a.intersection(&b).unwrap();
+        assert_eq!(Rational::new(43, 3), hit.point[0]);
+        assert_eq!(Rational::new(46, 3), hit.point[1]);
+        assert!(hit.t > Rational::from(0));
+        assert!(hit.s > Rational::from(0));
+    }
 }
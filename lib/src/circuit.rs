@@ -0,0 +1,544 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Debug,
+};
+
+use crate::{
+    cycle::Cycle,
+    diagram::{Diagram, Shape},
+};
+
+/// A module's position within its [`Circuit`], so pulses don't pass names around
+pub type ModuleId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    High,
+    Low,
+}
+
+/// How a module responds to the pulses it receives
+pub trait ModuleKind: Debug {
+    /// Called once for every module wired into this one
+    fn connect_input(&mut self, _from: ModuleId) {}
+
+    /// Handles a pulse, returning the pulse to send on to every output, if any
+    fn receive(&mut self, from: ModuleId, pulse: Pulse) -> Option<Pulse>;
+
+    /// Everything the module remembers, for [`Circuit::snapshot`]
+    fn state(&self) -> Vec<bool> {
+        vec![]
+    }
+
+    /// Puts back what [`ModuleKind::state`] returned
+    fn restore(&mut self, _state: &[bool]) {}
+
+    /// How the module is drawn in [`Circuit::diagram`]
+    fn shape(&self) -> Shape {
+        Shape::Box
+    }
+}
+
+/// Passes every pulse straight on
+#[derive(Debug, Clone, Default)]
+pub struct Broadcaster;
+
+impl ModuleKind for Broadcaster {
+    fn receive(&mut self, _from: ModuleId, pulse: Pulse) -> Option<Pulse> {
+        Some(pulse)
+    }
+
+    fn shape(&self) -> Shape {
+        Shape::Diamond
+    }
+}
+
+/// Ignores high pulses, a low pulse switches it on and sends high or off and sends low
+#[derive(Debug, Clone, Default)]
+pub struct FlipFlop {
+    pub on: bool,
+}
+
+impl ModuleKind for FlipFlop {
+    fn receive(&mut self, _from: ModuleId, pulse: Pulse) -> Option<Pulse> {
+        if pulse == Pulse::High {
+            return None;
+        }
+        self.on = !self.on;
+        Some(if self.on { Pulse::High } else { Pulse::Low })
+    }
+
+    fn state(&self) -> Vec<bool> {
+        vec![self.on]
+    }
+
+    fn restore(&mut self, state: &[bool]) {
+        self.on = state[0];
+    }
+}
+
+/// Remembers the last pulse from each input, starting low,
+/// and sends low once they are all high, otherwise high
+#[derive(Debug, Clone, Default)]
+pub struct Conjunction {
+    pub memory: Vec<(ModuleId, Pulse)>,
+}
+
+impl ModuleKind for Conjunction {
+    fn connect_input(&mut self, from: ModuleId) {
+        self.memory.push((from, Pulse::Low));
+    }
+
+    fn receive(&mut self, from: ModuleId, pulse: Pulse) -> Option<Pulse> {
+        if let Some(remembered) = self.memory.iter_mut().find(|(input, _)| *input == from) {
+            remembered.1 = pulse;
+        }
+        if self.memory.iter().all(|(_, p)| *p == Pulse::High) {
+            Some(Pulse::Low)
+        } else {
+            Some(Pulse::High)
+        }
+    }
+
+    fn state(&self) -> Vec<bool> {
+        self.memory.iter().map(|(_, p)| *p == Pulse::High).collect()
+    }
+
+    fn restore(&mut self, state: &[bool]) {
+        for ((_, p), high) in self.memory.iter_mut().zip(state) {
+            *p = if *high { Pulse::High } else { Pulse::Low };
+        }
+    }
+
+    fn shape(&self) -> Shape {
+        Shape::Ellipse
+    }
+}
+
+/// Receives pulses and does nothing with them, every module starts as one until given a kind
+#[derive(Debug, Clone, Default)]
+pub struct Output;
+
+impl ModuleKind for Output {
+    fn receive(&mut self, _from: ModuleId, _pulse: Pulse) -> Option<Pulse> {
+        None
+    }
+
+    fn shape(&self) -> Shape {
+        Shape::DoubleCircle
+    }
+}
+
+/// Told of every pulse as it is delivered
+pub trait InboxListener {
+    fn on_pulse(&mut self, from: ModuleId, to: ModuleId, pulse: Pulse);
+}
+
+impl InboxListener for () {
+    fn on_pulse(&mut self, _from: ModuleId, _to: ModuleId, _pulse: Pulse) {}
+}
+
+impl<F> InboxListener for F
+where
+    F: FnMut(ModuleId, ModuleId, Pulse),
+{
+    fn on_pulse(&mut self, from: ModuleId, to: ModuleId, pulse: Pulse) {
+        self(from, to, pulse)
+    }
+}
+
+/// Counts the high and low pulses delivered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PulseCount {
+    pub high: usize,
+    pub low: usize,
+}
+
+impl InboxListener for PulseCount {
+    fn on_pulse(&mut self, _from: ModuleId, _to: ModuleId, pulse: Pulse) {
+        match pulse {
+            Pulse::High => self.high += 1,
+            Pulse::Low => self.low += 1,
+        }
+    }
+}
+
+/// Modules wired together by name, passing pulses one at a time in the order they were sent.
+///
+/// ```
+/// use aoclib::circuit::{Broadcaster, Circuit, FlipFlop, Pulse, PulseCount};
+///
+/// let mut circuit = Circuit::new();
+/// circuit.add("in", Broadcaster, &["a"]);
+/// circuit.add("a", FlipFlop::default(), &["out"]);
+///
+/// let (button, input) = (circuit.id("button"), circuit.id("in"));
+/// let mut count = PulseCount::default();
+/// circuit.send(button, input, Pulse::Low, &mut count);
+/// circuit.send(button, input, Pulse::Low, &mut count);
+/// // button -> in -> a -> out, twice, with `a` sending high then low
+/// assert_eq!(PulseCount { high: 1, low: 5 }, count);
+/// ```
+#[derive(Debug, Default)]
+pub struct Circuit {
+    ids: HashMap<String, ModuleId>,
+    names: Vec<String>,
+    modules: Vec<Box<dyn ModuleKind>>,
+    inputs: Vec<Vec<ModuleId>>,
+    outputs: Vec<Vec<ModuleId>>,
+}
+
+impl Circuit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the module called `name`, adding it as an [`Output`] if it is new
+    pub fn id(&mut self, name: &str) -> ModuleId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.to_owned(), id);
+        self.names.push(name.to_owned());
+        self.modules.push(Box::new(Output));
+        self.inputs.push(vec![]);
+        self.outputs.push(vec![]);
+        id
+    }
+
+    pub fn find(&self, name: &str) -> Option<ModuleId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: ModuleId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn inputs(&self, id: ModuleId) -> &[ModuleId] {
+        &self.inputs[id]
+    }
+
+    pub fn outputs(&self, id: ModuleId) -> &[ModuleId] {
+        &self.outputs[id]
+    }
+
+    /// Makes `name` a module of `kind` sending to each of `outputs`
+    pub fn add(
+        &mut self,
+        name: &str,
+        kind: impl ModuleKind + 'static,
+        outputs: &[&str],
+    ) -> ModuleId {
+        let id = self.id(name);
+        let mut kind: Box<dyn ModuleKind> = Box::new(kind);
+        for input in &self.inputs[id] {
+            kind.connect_input(*input);
+        }
+        self.modules[id] = kind;
+
+        for output in outputs {
+            let to = self.id(output);
+            self.outputs[id].push(to);
+            self.inputs[to].push(id);
+            self.modules[to].connect_input(id);
+        }
+        id
+    }
+
+    /// Delivers `pulse` from `from` to `to`, then every pulse that causes,
+    /// until the circuit is quiet again
+    pub fn send(
+        &mut self,
+        from: ModuleId,
+        to: ModuleId,
+        pulse: Pulse,
+        listener: &mut impl InboxListener,
+    ) {
+        let mut inbox = VecDeque::from([(from, to, pulse)]);
+        while let Some((from, to, pulse)) = inbox.pop_front() {
+            listener.on_pulse(from, to, pulse);
+            if let Some(sent) = self.modules[to].receive(from, pulse) {
+                for output in &self.outputs[to] {
+                    inbox.push_back((to, *output, sent));
+                }
+            }
+        }
+    }
+
+    /// The state of every module, to [`Circuit::restore`] later
+    pub fn snapshot(&self) -> Vec<Vec<bool>> {
+        self.modules.iter().map(|m| m.state()).collect()
+    }
+
+    pub fn restore(&mut self, snapshot: &[Vec<bool>]) {
+        for (module, state) in self.modules.iter_mut().zip(snapshot) {
+            module.restore(state);
+        }
+    }
+
+    /// Every module, shaped by its kind, wired to its outputs
+    pub fn diagram(&self) -> Diagram {
+        let mut diagram = Diagram::directed();
+        for (name, module) in self.names.iter().zip(&self.modules) {
+            diagram.node(name, module.shape());
+        }
+        for (from, outputs) in self.outputs.iter().enumerate() {
+            for to in outputs {
+                diagram.edge(&self.names[from], &self.names[*to], None);
+            }
+        }
+        diagram
+    }
+
+    /// The modules reachable from each output of `root` and no other,
+    /// keyed by that output
+    pub fn subcircuits(&self, root: ModuleId) -> Vec<(ModuleId, Vec<ModuleId>)> {
+        let reachable: Vec<HashSet<ModuleId>> = self.outputs[root]
+            .iter()
+            .map(|start| {
+                let mut seen = HashSet::from([*start]);
+                let mut todo = vec![*start];
+                while let Some(m) = todo.pop() {
+                    for next in &self.outputs[m] {
+                        if *next != root && seen.insert(*next) {
+                            todo.push(*next);
+                        }
+                    }
+                }
+                seen
+            })
+            .collect();
+
+        self.outputs[root]
+            .iter()
+            .zip(&reachable)
+            .enumerate()
+            .map(|(i, (start, modules))| {
+                let mut only_here: Vec<ModuleId> = modules
+                    .iter()
+                    .filter(|m| {
+                        reachable
+                            .iter()
+                            .enumerate()
+                            .all(|(j, other)| i == j || !other.contains(m))
+                    })
+                    .copied()
+                    .collect();
+                only_here.sort();
+                (*start, only_here)
+            })
+            .collect()
+    }
+
+    /// How the state of each of `root`'s [`Circuit::subcircuits`] repeats as
+    /// `pulse` is sent from `from` to `root` over and over, giving up after `limit` sends.
+    ///
+    /// Circuits are often independent counters joined at the end, which only
+    /// line up after far too many sends to simulate, but each counter's
+    /// cycle can be found separately. The circuit is left as it was.
+    pub fn subcircuit_cycles(
+        &mut self,
+        from: ModuleId,
+        root: ModuleId,
+        pulse: Pulse,
+        limit: usize,
+    ) -> Option<Vec<(ModuleId, Cycle)>> {
+        let subcircuits = self.subcircuits(root);
+        let initial = self.snapshot();
+
+        let mut seen: Vec<HashMap<Vec<Vec<bool>>, usize>> = vec![HashMap::new(); subcircuits.len()];
+        let mut cycles: Vec<Option<Cycle>> = vec![None; subcircuits.len()];
+        for sends in 0..=limit {
+            for (i, (_, modules)) in subcircuits.iter().enumerate() {
+                if cycles[i].is_some() {
+                    continue;
+                }
+                let state = modules.iter().map(|m| self.modules[*m].state()).collect();
+                if let Some(start) = seen[i].insert(state, sends) {
+                    cycles[i] = Some(Cycle {
+                        start,
+                        length: sends - start,
+                    });
+                }
+            }
+            if cycles.iter().all(|c| c.is_some()) {
+                break;
+            }
+            self.send(from, root, pulse, &mut ());
+        }
+        self.restore(&initial);
+
+        subcircuits
+            .iter()
+            .zip(cycles)
+            .map(|((start, _), cycle)| Some((*start, cycle?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a broadcaster feeding two chains of flip flops, counting up in binary,
+    /// both feeding one conjunction
+    fn counters() -> Circuit {
+        let mut c = Circuit::new();
+        c.add("broadcaster", Broadcaster, &["a1", "b1"]);
+        c.add("a1", FlipFlop::default(), &["a2"]);
+        c.add("a2", FlipFlop::default(), &["join"]);
+        c.add("b1", FlipFlop::default(), &["b2"]);
+        c.add("b2", FlipFlop::default(), &["b3"]);
+        c.add("b3", FlipFlop::default(), &["join"]);
+        c.add("join", Conjunction::default(), &["out"]);
+        c
+    }
+
+    fn press(c: &mut Circuit, listener: &mut impl InboxListener) {
+        let (button, broadcaster) = (c.id("button"), c.id("broadcaster"));
+        c.send(button, broadcaster, Pulse::Low, listener);
+    }
+
+    #[test]
+    fn test_interned_ids() {
+        let mut c = counters();
+        assert_eq!(8, c.len());
+        let join = c.find("join").unwrap();
+        assert_eq!("join", c.name(join));
+        assert_eq!(join, c.id("join"));
+        assert_eq!(
+            vec!["a2", "b3"],
+            c.inputs(join)
+                .iter()
+                .map(|i| c.name(*i))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, c.find("missing"));
+    }
+
+    #[test]
+    fn test_conjunction_wired_before_its_inputs() {
+        let mut c = Circuit::new();
+        c.add("join", Conjunction::default(), &["out"]);
+        c.add("a", Broadcaster, &["join"]);
+        c.add("b", Broadcaster, &["join"]);
+
+        let (a, b, join) = (c.id("a"), c.id("b"), c.id("join"));
+        let mut received = vec![];
+        let mut record =
+            |from: ModuleId, to: ModuleId, pulse: Pulse| received.push((from, to, pulse));
+        c.send(a, a, Pulse::High, &mut record);
+        c.send(b, b, Pulse::High, &mut record);
+
+        let from_join: Vec<Pulse> = received
+            .iter()
+            .filter(|(from, _, _)| *from == join)
+            .map(|(_, _, p)| *p)
+            .collect();
+        assert_eq!(vec![Pulse::High, Pulse::Low], from_join);
+    }
+
+    #[test]
+    fn test_pulses_delivered_in_order() {
+        let mut c = counters();
+        press(&mut c, &mut ());
+        // the second press carries on through the flip flops which were switched on
+        let mut delivered = vec![];
+        press(&mut c, &mut |from: ModuleId, to: ModuleId, _: Pulse| {
+            delivered.push((from, to))
+        });
+        let names: Vec<String> = delivered
+            .iter()
+            .map(|(from, to)| format!("{}->{}", c.name(*from), c.name(*to)))
+            .collect();
+        assert_eq!(
+            vec![
+                "button->broadcaster",
+                "broadcaster->a1",
+                "broadcaster->b1",
+                "a1->a2",
+                "b1->b2",
+                "a2->join",
+                "b2->b3",
+                "join->out"
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut c = counters();
+        c.id("button");
+        let initial = c.snapshot();
+        press(&mut c, &mut ());
+        let pressed = c.snapshot();
+        assert_ne!(initial, pressed);
+
+        c.restore(&initial);
+        assert_eq!(initial, c.snapshot());
+        press(&mut c, &mut ());
+        assert_eq!(pressed, c.snapshot());
+    }
+
+    #[test]
+    fn test_diagram() {
+        let mut c = Circuit::new();
+        c.add("broadcaster", Broadcaster, &["a"]);
+        c.add("a", FlipFlop::default(), &["join"]);
+        c.add("join", Conjunction::default(), &["a", "out"]);
+        let expected = r#"digraph {
+    "broadcaster" [shape=diamond];
+    "a" [shape=box];
+    "join" [shape=ellipse];
+    "out" [shape=doublecircle];
+    "broadcaster" -> "a";
+    "a" -> "join";
+    "join" -> "a";
+    "join" -> "out";
+}
+"#;
+        assert_eq!(expected, c.diagram().to_dot());
+    }
+
+    #[test]
+    fn test_subcircuits() {
+        let mut c = counters();
+        let broadcaster = c.id("broadcaster");
+        let names = |ids: &[ModuleId]| ids.iter().map(|i| c.name(*i)).collect::<Vec<_>>();
+        let subcircuits = c.subcircuits(broadcaster);
+        assert_eq!(2, subcircuits.len());
+        assert_eq!("a1", c.name(subcircuits[0].0));
+        assert_eq!(vec!["a1", "a2"], names(&subcircuits[0].1));
+        assert_eq!(vec!["b1", "b2", "b3"], names(&subcircuits[1].1));
+    }
+
+    #[test]
+    fn test_subcircuit_cycles() {
+        let mut c = counters();
+        let (button, broadcaster) = (c.id("button"), c.id("broadcaster"));
+        let initial = c.snapshot();
+
+        let cycles = c
+            .subcircuit_cycles(button, broadcaster, Pulse::Low, 100)
+            .unwrap();
+        let lengths: Vec<usize> = cycles.iter().map(|(_, cycle)| cycle.length).collect();
+        assert_eq!(vec![4, 8], lengths);
+        assert!(cycles.iter().all(|(_, cycle)| cycle.start == 0));
+        assert_eq!(initial, c.snapshot());
+
+        assert_eq!(
+            None,
+            c.subcircuit_cycles(button, broadcaster, Pulse::Low, 5)
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states, each produced from the previous one, starts repeating.
+///
+/// State `0` is the initial state, state `n` is the result of `n` steps.
+/// States from `start` onwards repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as the state after `n` steps
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle using
+/// [Floyd's tortoise and hare](https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare),
+/// holding only a couple of states at a time.
+///
+/// States are considered the same when their `key`s are equal.
+/// Never returns if the states never repeat.
+pub fn floyd<S, K, F, G>(initial: S, mut step: F, key: G) -> Cycle
+where
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+    K: PartialEq,
+{
+    let same = |a: &S, b: &S| key(a) == key(b);
+
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while !same(&tortoise, &hare) {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while !same(&tortoise, &hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while !same(&tortoise, &hare) {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle using
+/// [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm),
+/// which usually needs fewer steps than [`floyd`].
+///
+/// States are considered the same when their `key`s are equal.
+/// Never returns if the states never repeat.
+pub fn brent<S, K, F, G>(initial: S, mut step: F, key: G) -> Cycle
+where
+    S: Clone,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+    K: PartialEq,
+{
+    let same = |a: &S, b: &S| key(a) == key(b);
+
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while !same(&tortoise, &hare) {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while !same(&tortoise, &hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Every distinct state up to the first repeat, along with the [`Cycle`] they make
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `n` steps, however large `n` is
+    pub fn after(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+
+    /// States in the order they were first seen, ending with the last state of the cycle
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Steps from `initial`, remembering each state's `key` until one repeats.
+///
+/// Uses more memory than [`floyd`] or [`brent`] but visits each state only once,
+/// which matters when a step is expensive.
+/// Never returns if the states never repeat.
+pub fn history<S, K, F, G>(initial: S, mut step: F, key: G) -> History<S>
+where
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+    K: Hash + Eq,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut current = initial;
+    loop {
+        if let Some(start) = seen.insert(key(&current), states.len()) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return History { cycle, states };
+        }
+        let next = step(&current);
+        states.push(current);
+        current = next;
+    }
+}
+
+/// The state after `n` steps from `initial`, skipping whole cycles
+/// so `n` can be far larger than could ever be simulated.
+///
+/// ```
+/// use aoclib::cycle::state_after;
+///
+/// // doubling modulo 300 from 3 goes 3, 6, 12, ... and repeats every 20 steps from 12
+/// let n = state_after(3_u32, |x| x * 2 % 300, |x| *x, 1_000_000_000);
+/// assert_eq!(228, n);
+/// ```
+pub fn state_after<S, K, F, G>(initial: S, mut step: F, key: G, n: usize) -> S
+where
+    S: Clone,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+    K: PartialEq,
+{
+    let cycle = brent(initial.clone(), &mut step, key);
+    (0..cycle.equivalent_step(n)).fold(initial, |state, _| step(&state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 ...
+    fn rho(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_floyd() {
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 4
+            },
+            floyd(0, rho, |x| *x)
+        );
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 4
+            },
+            floyd(3, rho, |x| *x)
+        );
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 4
+            },
+            brent(0, rho, |x| *x)
+        );
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 4
+            },
+            brent(3, rho, |x| *x)
+        );
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 1
+            },
+            brent(7, |x| *x, |x| *x)
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        let h = history(0, rho, |x| *x);
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 4
+            },
+            h.cycle
+        );
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6], h.states());
+        assert_eq!(&2, h.after(2));
+        assert_eq!(&3, h.after(7));
+        assert_eq!(&6, h.after(1_000_000_002));
+    }
+
+    #[test]
+    fn test_equivalent_step() {
+        let c = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(2, c.equivalent_step(2));
+        assert_eq!(3, c.equivalent_step(3));
+        assert_eq!(6, c.equivalent_step(6));
+        assert_eq!(3, c.equivalent_step(7));
+        assert_eq!(4, c.equivalent_step(1_000_000_000));
+    }
+
+    #[test]
+    fn test_key_ignores_extra_state() {
+        // the step count is carried along but isn't part of what repeats
+        let step = |(x, count): &(u32, usize)| (rho(x), count + 1);
+        let key = |(x, _): &(u32, usize)| *x;
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 4
+            },
+            floyd((0, 0), step, key)
+        );
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 4
+            },
+            brent((0, 0), step, key)
+        );
+        assert_eq!(
+            Cycle {
+                start: 3,
+                length: 4
+            },
+            history((0, 0), step, key).cycle
+        );
+        assert_eq!((5, 5), state_after((0, 0), step, key, 9));
+    }
+
+    #[test]
+    fn test_state_after() {
+        assert_eq!(4, state_after(0, rho, |x| *x, 1_000_000_000));
+        assert_eq!(1, state_after(0, rho, |x| *x, 1));
+    }
+}
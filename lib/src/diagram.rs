@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+/// How a node is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Box,
+    Ellipse,
+    Circle,
+    DoubleCircle,
+    Diamond,
+}
+
+impl Shape {
+    fn dot(&self) -> &'static str {
+        match self {
+            Shape::Box => "box",
+            Shape::Ellipse => "ellipse",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+            Shape::Diamond => "diamond",
+        }
+    }
+
+    fn mermaid(&self) -> (&'static str, &'static str) {
+        match self {
+            Shape::Box => ("[", "]"),
+            Shape::Ellipse => ("(", ")"),
+            Shape::Circle => ("((", "))"),
+            Shape::DoubleCircle => ("(((", ")))"),
+            Shape::Diamond => ("{", "}"),
+        }
+    }
+}
+
+/// A graph of named nodes to be drawn, as [Graphviz](https://graphviz.org) DOT
+/// or [Mermaid](https://mermaid.js.org) text.
+///
+/// Nodes and edges are written in the order they were added, so the text is stable.
+///
+/// ```
+/// use aoclib::diagram::{Diagram, Shape};
+///
+/// let mut d = Diagram::directed();
+/// d.node("in", Shape::Box);
+/// d.edge("in", "A", Some("x<10"));
+/// assert_eq!(
+///     "digraph {\n    \"in\" [shape=box];\n    \"A\" [shape=ellipse];\n    \"in\" -> \"A\" [label=\"x<10\"];\n}\n",
+///     d.to_dot()
+/// );
+/// assert_eq!(
+///     "flowchart LR\n    n0[\"in\"]\n    n1(\"A\")\n    n0 -->|\"x<10\"| n1\n",
+///     d.to_mermaid()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagram {
+    directed: bool,
+    ids: HashMap<String, usize>,
+    nodes: Vec<(String, Shape)>,
+    edges: Vec<(usize, usize, Option<String>)>,
+}
+
+impl Diagram {
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Diagram {
+            directed,
+            ids: HashMap::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds `name` drawn as `shape`, or reshapes it if it is already there
+    pub fn node(&mut self, name: &str, shape: Shape) -> &mut Self {
+        let id = self.id(name);
+        self.nodes[id].1 = shape;
+        self
+    }
+
+    /// Joins `from` to `to`, adding either as an [`Shape::Ellipse`] if it is new
+    pub fn edge(&mut self, from: &str, to: &str, label: Option<&str>) -> &mut Self {
+        let (from, to) = (self.id(from), self.id(to));
+        self.edges.push((from, to, label.map(str::to_owned)));
+        self
+    }
+
+    fn id(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(name.to_owned(), id);
+        self.nodes.push((name.to_owned(), Shape::Ellipse));
+        id
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (graph, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut out = format!("{graph} {{\n");
+        for (name, shape) in &self.nodes {
+            out += &format!("    {} [shape={}];\n", quoted(name), shape.dot());
+        }
+        for (from, to, label) in &self.edges {
+            out += &format!(
+                "    {} {arrow} {}",
+                quoted(&self.nodes[*from].0),
+                quoted(&self.nodes[*to].0)
+            );
+            if let Some(label) = label {
+                out += &format!(" [label={}]", quoted(label));
+            }
+            out += ";\n";
+        }
+        out + "}\n"
+    }
+
+    /// Nodes are numbered rather than named, as names like `end` mean something to Mermaid
+    pub fn to_mermaid(&self) -> String {
+        let arrow = if self.directed { "-->" } else { "---" };
+        let mut out = String::from("flowchart LR\n");
+        for (id, (name, shape)) in self.nodes.iter().enumerate() {
+            let (open, close) = shape.mermaid();
+            out += &format!("    n{id}{open}{}{close}\n", mermaid_text(name));
+        }
+        for (from, to, label) in &self.edges {
+            match label {
+                Some(label) => {
+                    out += &format!("    n{from} {arrow}|{}| n{to}\n", mermaid_text(label))
+                }
+                None => out += &format!("    n{from} {arrow} n{to}\n"),
+            }
+        }
+        out
+    }
+}
+
+fn quoted(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_text(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "#quot;"))
+}
+
+/// The diagram as text when `args` ask for it with `--dot` or `--mermaid`,
+/// so a day can be run to draw its input rather than solve it.
+///
+/// ```
+/// use aoclib::diagram::{requested, Diagram};
+///
+/// let args = ["d20", "--mermaid"].map(String::from);
+/// assert_eq!(Some("flowchart LR\n".to_owned()), requested(args, Diagram::directed));
+/// assert_eq!(None, requested(["d20".to_owned()], Diagram::directed));
+/// ```
+pub fn requested<F>(args: impl IntoIterator<Item = String>, diagram: F) -> Option<String>
+where
+    F: FnOnce() -> Diagram,
+{
+    args.into_iter()
+        .find_map(|arg| match arg.as_str() {
+            "--dot" => Some(Diagram::to_dot as fn(&Diagram) -> String),
+            "--mermaid" => Some(Diagram::to_mermaid as fn(&Diagram) -> String),
+            _ => None,
+        })
+        .map(|write| write(&diagram()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caves() -> Diagram {
+        let mut d = Diagram::undirected();
+        d.node("start", Shape::DoubleCircle)
+            .node("end", Shape::DoubleCircle)
+            .edge("start", "A", None)
+            .edge("A", "b", None)
+            .edge("b", "end", None)
+            .node("A", Shape::Box);
+        d
+    }
+
+    #[test]
+    fn test_undirected_dot() {
+        let expected = r#"graph {
+    "start" [shape=doublecircle];
+    "end" [shape=doublecircle];
+    "A" [shape=box];
+    "b" [shape=ellipse];
+    "start" -- "A";
+    "A" -- "b";
+    "b" -- "end";
+}
+"#;
+        assert_eq!(expected, caves().to_dot());
+    }
+
+    #[test]
+    fn test_undirected_mermaid() {
+        let expected = r#"flowchart LR
+    n0((("start")))
+    n1((("end")))
+    n2["A"]
+    n3("b")
+    n0 --- n2
+    n2 --- n3
+    n3 --- n1
+"#;
+        assert_eq!(expected, caves().to_mermaid());
+    }
+
+    #[test]
+    fn test_quoting() {
+        let mut d = Diagram::directed();
+        d.edge("say \"hi\"", "a\\b", Some("\"quoted\""));
+        assert_eq!(
+            "digraph {\n    \"say \\\"hi\\\"\" [shape=ellipse];\n    \"a\\\\b\" [shape=ellipse];\n    \"say \\\"hi\\\"\" -> \"a\\\\b\" [label=\"\\\"quoted\\\"\"];\n}\n",
+            d.to_dot()
+        );
+        assert!(d.to_mermaid().contains("n0 -->|\"#quot;quoted#quot;\"| n1"));
+    }
+
+    #[test]
+    fn test_requested_is_lazy() {
+        let args = ["d1".to_owned()];
+        assert_eq!(None, requested(args, || panic!("not asked for")));
+        assert_eq!(
+            Some(caves().to_dot()),
+            requested(["--dot".to_owned()], caves)
+        );
+    }
+}
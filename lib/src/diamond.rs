@@ -0,0 +1,298 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    cartesian::{Plane, Point, Vector},
+    distance::{ManhattenDistance, Metric},
+};
+
+/// All the points within a manhatten distance of a centre point,
+/// which forms a diamond shape.
+///
+/// ```ignore
+/// ..#..
+/// .###.
+/// ##C##
+/// .###.
+/// ..#..
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diamond {
+    pub centre: Point,
+    pub radius: i64,
+}
+
+impl Diamond {
+    pub fn new(centre: Point, radius: i64) -> Self {
+        if radius < 0 {
+            panic!("radius must not be negative but was {radius}");
+        }
+        Diamond { centre, radius }
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        ManhattenDistance::between(&self.centre, p).0 <= self.radius
+    }
+
+    /// The inclusive range of x values covered on row `y`
+    pub fn row(&self, y: i64) -> Option<RangeInclusive<i64>> {
+        let half_width = self.radius - (y - self.centre.y).abs();
+        if half_width < 0 {
+            None
+        } else {
+            Some(self.centre.x - half_width..=self.centre.x + half_width)
+        }
+    }
+
+    /// The top, right, bottom and left corners
+    pub fn corners(&self) -> [Point; 4] {
+        let Point { x, y } = self.centre;
+        let r = self.radius;
+        [
+            (x, y + r).into(),
+            (x + r, y).into(),
+            (x, y - r).into(),
+            (x - r, y).into(),
+        ]
+    }
+
+    /// The four diagonal edges running clockwise from the top corner
+    pub fn edges(&self) -> [Vector; 4] {
+        let [top, right, bottom, left] = self.corners();
+        [
+            top.clone().to(right.clone()),
+            right.to(bottom.clone()),
+            bottom.to(left.clone()),
+            left.to(top),
+        ]
+    }
+
+    /// Every point exactly `radius` from the centre, each once
+    pub fn boundary(&self) -> impl Iterator<Item = Point> + '_ {
+        let edges = self.edges();
+        let single = if self.radius == 0 {
+            Some(self.centre.clone())
+        } else {
+            None
+        };
+        let edge_points = self.radius as usize;
+        single.into_iter().chain(
+            edges
+                .into_iter()
+                .flat_map(move |e| e.lattice_points().take(edge_points).collect::<Vec<_>>()),
+        )
+    }
+
+    /// The diamond rotated 45° to an axis aligned square, using `u = x + y` and `v = x - y`.
+    ///
+    /// Only points where `u` and `v` are both odd or both even map back to integer points.
+    pub fn rotated(&self) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
+        let u = self.centre.x + self.centre.y;
+        let v = self.centre.x - self.centre.y;
+        (
+            u - self.radius..=u + self.radius,
+            v - self.radius..=v + self.radius,
+        )
+    }
+}
+
+impl From<Vector> for Diamond {
+    /// The diamond centred on the start, reaching out to the end
+    fn from(value: Vector) -> Self {
+        let ManhattenDistance(radius) = ManhattenDistance::between(&value.start, &value.end);
+        Diamond::new(value.start, radius)
+    }
+}
+
+/// The x values covered by any of the diamonds on row `y`,
+/// as sorted, disjoint and non touching ranges.
+pub fn row_coverage(diamonds: &[Diamond], y: i64) -> Vec<RangeInclusive<i64>> {
+    let mut rows: Vec<_> = diamonds.iter().filter_map(|d| d.row(y)).collect();
+    rows.sort_by_key(|r| *r.start());
+
+    let mut merged: Vec<RangeInclusive<i64>> = Vec::new();
+    for r in rows {
+        match merged.last_mut() {
+            Some(last) if *r.start() <= *last.end() + 1 => {
+                if r.end() > last.end() {
+                    *last = *last.start()..=*r.end();
+                }
+            }
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// Finds a point within the plane not covered by any of the diamonds.
+///
+/// In [`Diamond::rotated`] coordinates every diamond is a square, so an uncovered
+/// point can be slid left, then along the line it ends up against, until it is
+/// pinned where the lines just outside the squares' edges cross each other or the
+/// edges of the plane. Only those crossings need checking, which takes no longer
+/// for very large planes than for small ones.
+pub fn find_uncovered(diamonds: &[Diamond], plane: &Plane) -> Option<Point> {
+    let min_x = plane.top_left.x.min(plane.bottom_right.x);
+    let max_x = plane.top_left.x.max(plane.bottom_right.x);
+    let min_y = plane.top_left.y.min(plane.bottom_right.y);
+    let max_y = plane.top_left.y.max(plane.bottom_right.y);
+
+    // a point one step along a diagonal moves two in u or v,
+    // so it can be pinned one or two away from a square's edge
+    let mut us = Vec::new();
+    let mut vs = Vec::new();
+    for d in diamonds {
+        let (u, v) = d.rotated();
+        for gap in [1, 2] {
+            us.extend([u.start() - gap, u.end() + gap]);
+            vs.extend([v.start() - gap, v.end() + gap]);
+        }
+    }
+
+    let mut candidates = vec![
+        (min_x, min_y),
+        (min_x, max_y),
+        (max_x, min_y),
+        (max_x, max_y),
+    ];
+    for u in &us {
+        for v in &vs {
+            if (u + v).rem_euclid(2) == 0 {
+                candidates.push(((u + v) / 2, (u - v) / 2));
+            }
+        }
+    }
+    for x in [min_x, max_x] {
+        candidates.extend(us.iter().map(|u| (x, u - x)));
+        candidates.extend(vs.iter().map(|v| (x, x - v)));
+    }
+    for y in [min_y, max_y] {
+        candidates.extend(us.iter().map(|u| (u - y, y)));
+        candidates.extend(vs.iter().map(|v| (v + y, y)));
+    }
+
+    candidates
+        .into_iter()
+        .filter(|(x, y)| (min_x..=max_x).contains(x) && (min_y..=max_y).contains(y))
+        .map(Point::from)
+        .find(|p| !diamonds.iter().any(|d| d.contains(p)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_diamond_row() {
+        // ..B..
+        // .###.
+        // ##S##
+        // .###.
+        // ..#..
+        let d: Diamond = Vector::from(((2, 2).into(), (2, 4).into())).into();
+
+        assert_eq!(2, d.radius);
+        assert_eq!(Some(2..=2), d.row(0));
+        assert_eq!(Some(1..=3), d.row(1));
+        assert_eq!(Some(0..=4), d.row(2));
+        assert_eq!(Some(2..=2), d.row(4));
+        assert_eq!(None, d.row(5));
+        assert_eq!(None, d.row(-1));
+    }
+
+    #[test]
+    fn test_diamond_contains() {
+        let d = Diamond::new((0, 0).into(), 2);
+        assert!(d.contains(&(1, 1).into()));
+        assert!(d.contains(&(0, -2).into()));
+        assert!(!d.contains(&(2, 1).into()));
+    }
+
+    #[test]
+    fn test_diamond_boundary() {
+        let d = Diamond::new((5, 5).into(), 3);
+        let boundary: Vec<Point> = d.boundary().collect();
+        let unique: HashSet<Point> = boundary.iter().cloned().collect();
+
+        assert_eq!(12, boundary.len());
+        assert_eq!(12, unique.len());
+        assert!(boundary
+            .iter()
+            .all(|p| ManhattenDistance::between(&d.centre, p).0 == 3));
+
+        let dot = Diamond::new((1, 1).into(), 0);
+        assert_eq!(vec![Point::new(1, 1)], dot.boundary().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_diamond_rotated() {
+        let d = Diamond::new((3, 1).into(), 2);
+        assert_eq!((2..=6, 0..=4), d.rotated());
+    }
+
+    #[test]
+    fn test_row_coverage_merges() {
+        let diamonds = vec![
+            Diamond::new((0, 0).into(), 2),
+            Diamond::new((5, 0).into(), 2),
+            Diamond::new((4, 1).into(), 1),
+            Diamond::new((20, 0).into(), 1),
+        ];
+        assert_eq!(vec![-2..=7, 19..=21], row_coverage(&diamonds, 0));
+        assert_eq!(vec![-1..=1, 3..=6, 20..=20], row_coverage(&diamonds, 1));
+        assert!(row_coverage(&diamonds, 10).is_empty());
+    }
+
+    #[test]
+    fn test_find_uncovered() {
+        let plane = Plane {
+            top_left: (0, 2).into(),
+            bottom_right: (4, 0).into(),
+        };
+        let covering = vec![Diamond::new((2, 1).into(), 3)];
+        assert_eq!(None, find_uncovered(&covering, &plane));
+
+        // #####
+        // ###.#
+        // #####
+        let gap = vec![
+            Diamond::new((0, 0).into(), 2),
+            Diamond::new((4, 0).into(), 1),
+            Diamond::new((2, 1).into(), 0),
+            Diamond::new((1, 2).into(), 0),
+            Diamond::new((2, 2).into(), 0),
+            Diamond::new((3, 2).into(), 0),
+            Diamond::new((4, 2).into(), 0),
+        ];
+        assert_eq!(Some(Point::new(3, 1)), find_uncovered(&gap, &plane));
+    }
+
+    #[test]
+    fn test_find_uncovered_matches_every_point() {
+        // a small linear congruential generator, for repeatable diamonds
+        let mut seed: i64 = 7;
+        let mut next = |n: i64| {
+            seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+            seed % n
+        };
+        let plane = Plane {
+            top_left: (-3, 8).into(),
+            bottom_right: (9, -2).into(),
+        };
+        for _ in 0..300 {
+            let diamonds: Vec<Diamond> = (0..1 + next(12))
+                .map(|_| Diamond::new((next(16) - 5, next(14) - 4).into(), next(6)))
+                .collect();
+            let uncovered: HashSet<Point> = (-3..=9)
+                .flat_map(|x| (-2..=8).map(move |y| Point::new(x, y)))
+                .filter(|p| !diamonds.iter().any(|d| d.contains(p)))
+                .collect();
+
+            match find_uncovered(&diamonds, &plane) {
+                Some(p) => assert!(uncovered.contains(&p), "{p:?} in {diamonds:?}"),
+                None => assert!(uncovered.is_empty(), "missed a point in {diamonds:?}"),
+            }
+        }
+    }
+}
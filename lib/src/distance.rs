@@ -1,6 +1,71 @@
 use std::iter;
 
-use crate::cartesian::Vector;
+use crate::{
+    cartesian::{Point, Point3, Vector},
+    grid::GridPosition,
+};
+
+/// Anything that can be located by integer coordinates along some
+/// number of axes. Two values compared by a [`Metric`] should always
+/// have the same number of axes.
+pub trait Coordinates {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_;
+}
+
+impl Coordinates for Point {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_ {
+        [self.x, self.y].into_iter()
+    }
+}
+
+impl Coordinates for Point3 {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_ {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
+impl Coordinates for GridPosition {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_ {
+        [self.col as i64, self.row as i64].into_iter()
+    }
+}
+
+impl<const N: usize> Coordinates for [i64; N] {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl Coordinates for Vec<i64> {
+    fn coordinates(&self) -> impl Iterator<Item = i64> + '_ {
+        self.iter().copied()
+    }
+}
+
+fn axis_deltas<'a, P>(a: &'a P, b: &'a P) -> impl Iterator<Item = u64> + 'a
+where
+    P: Coordinates,
+{
+    a.coordinates()
+        .zip(b.coordinates())
+        .map(|(from, to)| from.abs_diff(to))
+}
+
+/// A way of measuring the distance between two [`Coordinates`]
+///
+/// ```
+/// use aoclib::distance::{ManhattenDistance, Metric, StraightLineDistance};
+///
+/// let a = [0, 0, 0];
+/// let b = [1, -2, 3];
+/// assert_eq!(ManhattenDistance(6), ManhattenDistance::between(&a, &b));
+/// assert_eq!(StraightLineDistance(3), StraightLineDistance::between(&a, &b));
+/// ```
+pub trait Metric {
+    fn between<P>(a: &P, b: &P) -> Self
+    where
+        P: Coordinates;
+}
 
 pub trait Distance {
     fn from_vector(v: Vector) -> Self;
@@ -14,24 +79,41 @@ pub trait Distance {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl<M> Distance for M
+where
+    M: Metric,
+{
+    fn from_vector(v: Vector) -> Self {
+        M::between(&v.start, &v.end)
+    }
+}
+
+/// The number of king moves, also known as the Chebyshev distance.
+/// The largest difference along any one axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StraightLineDistance(pub i64);
 
-impl Distance for StraightLineDistance {
-    fn from_vector(v: Vector) -> Self {
-        let x_delta = (v.start.x - v.end.x).abs();
-        let y_delta = (v.start.y - v.end.y).abs();
-        let xy_delta = (v.start.x - v.end.y).abs();
-        StraightLineDistance(x_delta.max(y_delta).max(xy_delta))
+pub type ChebyshevDistance = StraightLineDistance;
+
+impl Metric for StraightLineDistance {
+    fn between<P>(a: &P, b: &P) -> Self
+    where
+        P: Coordinates,
+    {
+        StraightLineDistance(axis_deltas(a, b).max().unwrap_or(0) as i64)
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+/// The sum of the differences along each axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ManhattenDistance(pub i64);
 
-impl Distance for ManhattenDistance {
-    fn from_vector(v: Vector) -> Self {
-        ManhattenDistance((v.start.x - v.end.x).abs() + (v.start.y - v.end.y).abs())
+impl Metric for ManhattenDistance {
+    fn between<P>(a: &P, b: &P) -> Self
+    where
+        P: Coordinates,
+    {
+        ManhattenDistance(axis_deltas(a, b).sum::<u64>() as i64)
     }
 }
 
@@ -41,16 +123,57 @@ impl iter::Sum for ManhattenDistance {
     }
 }
 
-// how do I add a blanked implementation for any Distance
-impl From<Vector> for StraightLineDistance {
-    fn from(value: Vector) -> Self {
-        Self::from_vector(value)
+/// The length of a straight line between the two.
+/// Prefer [`SquaredEuclideanDistance`] when only comparing distances.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct EuclideanDistance(pub f64);
+
+impl Metric for EuclideanDistance {
+    fn between<P>(a: &P, b: &P) -> Self
+    where
+        P: Coordinates,
+    {
+        EuclideanDistance((SquaredEuclideanDistance::between(a, b).0 as f64).sqrt())
     }
 }
 
+/// The euclidean distance squared, which stays an exact integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SquaredEuclideanDistance(pub i64);
+
+impl Metric for SquaredEuclideanDistance {
+    fn between<P>(a: &P, b: &P) -> Self
+    where
+        P: Coordinates,
+    {
+        SquaredEuclideanDistance(axis_deltas(a, b).map(|d| (d * d) as i64).sum())
+    }
+}
+
+// a blanket `impl<D: Distance> From<Vector> for D` conflicts with
+// the core `impl<T> From<T> for T`, so each metric gets its own
+macro_rules! from_vector {
+    ($($distance:ty),*) => {
+        $(
+            impl From<Vector> for $distance {
+                fn from(value: Vector) -> Self {
+                    Self::from_vector(value)
+                }
+            }
+        )*
+    };
+}
+
+from_vector!(
+    StraightLineDistance,
+    ManhattenDistance,
+    EuclideanDistance,
+    SquaredEuclideanDistance
+);
+
 #[cfg(test)]
 mod tests {
-    use crate::cartesian::Point;
+    use crate::cartesian::{Point, Point3};
 
     use super::*;
 
@@ -131,4 +254,43 @@ mod tests {
         let v: Vector = (p1, p2).into();
         assert_eq!(StraightLineDistance(3), v.into());
     }
+
+    #[test]
+    fn test_straight_line_ignores_mixed_axes() {
+        // x of one and y of the other are unrelated
+        let v: Vector = ((10, 0).into(), (10, 1).into()).into();
+        assert_eq!(StraightLineDistance(1), v.into());
+    }
+
+    #[test]
+    fn test_metrics_3d() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(-1, 5, 9);
+        assert_eq!(StraightLineDistance(6), ChebyshevDistance::between(&a, &b));
+        assert_eq!(ManhattenDistance(11), ManhattenDistance::between(&a, &b));
+        assert_eq!(
+            SquaredEuclideanDistance(49),
+            SquaredEuclideanDistance::between(&a, &b)
+        );
+        assert_eq!(EuclideanDistance(7.0), EuclideanDistance::between(&a, &b));
+    }
+
+    #[test]
+    fn test_metrics_any_dimension() {
+        let a = [0, 0, 0, 0];
+        let b = [1, -1, 2, -2];
+        assert_eq!(ManhattenDistance(6), ManhattenDistance::between(&a, &b));
+        assert_eq!(
+            SquaredEuclideanDistance(10),
+            SquaredEuclideanDistance::between(&a, &b)
+        );
+    }
+
+    #[test]
+    fn test_from_vector() {
+        let v: Vector = ((0, 0).into(), (3, 4).into()).into();
+        assert_eq!(ManhattenDistance(7), v.clone().into());
+        assert_eq!(EuclideanDistance(5.0), v.clone().into());
+        assert_eq!(SquaredEuclideanDistance(25), v.into());
+    }
 }
@@ -0,0 +1,354 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::cartesian::{Point, Point3};
+
+/// What lies beneath everything that has been placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Floor {
+    /// Anything falling past the lowest obstacle falls forever
+    Abyss,
+    /// An endless floor along this row, nothing can rest on or below it
+    Solid(i64),
+}
+
+/// Grains falling one at a time onto obstacles, each coming to rest before the next falls.
+///
+/// `y` grows downwards, a grain falls straight down if it can,
+/// otherwise down and to the left, otherwise down and to the right.
+#[derive(Debug, Clone)]
+pub struct SandPile {
+    blocked: HashSet<Point>,
+    rested: Vec<Point>,
+    floor: Floor,
+    lowest: i64,
+}
+
+impl SandPile {
+    pub fn new(obstacles: impl IntoIterator<Item = Point>, floor: Floor) -> Self {
+        let blocked: HashSet<Point> = obstacles.into_iter().collect();
+        let lowest = blocked.iter().map(|p| p.y).max().unwrap_or(0);
+        SandPile {
+            blocked,
+            rested: Vec::new(),
+            floor,
+            lowest,
+        }
+    }
+
+    pub fn is_blocked(&self, p: &Point) -> bool {
+        match self.floor {
+            Floor::Solid(y) if p.y >= y => true,
+            _ => self.blocked.contains(p),
+        }
+    }
+
+    /// Every grain which has come to rest, in the order they did
+    pub fn rested(&self) -> &[Point] {
+        &self.rested
+    }
+
+    /// Where a grain falling from `p` moves next, `None` if it is at rest
+    fn next(&self, p: &Point) -> Option<Point> {
+        [0, -1, 1]
+            .into_iter()
+            .map(|dx| Point::new(p.x + dx, p.y + 1))
+            .find(|n| !self.is_blocked(n))
+    }
+
+    fn falls_forever(&self, p: &Point) -> bool {
+        self.floor == Floor::Abyss && p.y > self.lowest
+    }
+
+    /// Drops a single grain from `source`, returning where it comes to rest.
+    /// `None` if it falls forever or `source` is already blocked.
+    pub fn drop_from(&mut self, source: Point) -> Option<Point> {
+        if self.is_blocked(&source) {
+            return None;
+        }
+        let mut p = source;
+        while let Some(next) = self.next(&p) {
+            if self.falls_forever(&next) {
+                return None;
+            }
+            p = next;
+        }
+        self.blocked.insert(p.clone());
+        self.rested.push(p.clone());
+        Some(p)
+    }
+
+    /// Drops grains from `source` until one falls forever or `source` is blocked,
+    /// returning where each came to rest.
+    ///
+    /// Each grain follows the last one's path until it is blocked,
+    /// so rather than starting from the top every time we back up that path.
+    pub fn fill(&mut self, source: Point) -> Vec<Point> {
+        let before = self.rested.len();
+        let mut path = vec![source];
+        while let Some(p) = path.last() {
+            if self.is_blocked(p) {
+                path.pop();
+                continue;
+            }
+            match self.next(p) {
+                Some(next) if self.falls_forever(&next) => break,
+                Some(next) => path.push(next),
+                None => {
+                    let p = path.pop().unwrap();
+                    self.blocked.insert(p.clone());
+                    self.rested.push(p);
+                }
+            }
+        }
+        self.rested[before..].to_vec()
+    }
+}
+
+/// An axis aligned box, `min` and `max` are both inside it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Cuboid {
+    /// The box between two opposite corners, in either order
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Cuboid {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    pub fn overlaps(&self, other: &Cuboid) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// This box moved down by `dz`
+    pub fn lowered(&self, dz: i64) -> Cuboid {
+        Cuboid {
+            min: Point3::new(self.min.x, self.min.y, self.min.z - dz),
+            max: Point3::new(self.max.x, self.max.y, self.max.z - dz),
+        }
+    }
+
+    /// The `(x, y)` of every column the box sits over
+    fn footprint(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        (self.min.x..=self.max.x).flat_map(move |x| (self.min.y..=self.max.y).map(move |y| (x, y)))
+    }
+}
+
+/// Where a stack of boxes came to rest and which boxes hold up which
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settled {
+    /// Each box at rest, in the order they were given
+    pub cuboids: Vec<Cuboid>,
+    /// The boxes each one rests directly on, none if it is on the floor
+    pub supported_by: Vec<Vec<usize>>,
+    /// The boxes resting directly on each one
+    pub supports: Vec<Vec<usize>>,
+}
+
+/// Lets every box fall straight down (decreasing `z`) until it lands on the floor
+/// or another box. Nothing can rest below `floor`.
+///
+/// Boxes fall lowest first, tracking the top of every column as they land,
+/// so a box only looks at the columns beneath it rather than every other box.
+pub fn settle(cuboids: &[Cuboid], floor: i64) -> Settled {
+    let mut order: Vec<usize> = (0..cuboids.len()).collect();
+    order.sort_by_key(|i| cuboids[*i].min.z);
+
+    // the highest z of each column, and the box it belongs to
+    let mut tops: HashMap<(i64, i64), (i64, usize)> = HashMap::new();
+    let mut settled = Settled {
+        cuboids: cuboids.to_vec(),
+        supported_by: vec![vec![]; cuboids.len()],
+        supports: vec![vec![]; cuboids.len()],
+    };
+
+    for i in order {
+        let cuboid = &cuboids[i];
+        let below: Vec<(i64, usize)> = cuboid
+            .footprint()
+            .filter_map(|column| tops.get(&column).copied())
+            .collect();
+        let landing = below
+            .iter()
+            .map(|(top, _)| top + 1)
+            .max()
+            .unwrap_or(floor)
+            .max(floor);
+        let supporters: BTreeSet<usize> = below
+            .iter()
+            .filter(|(top, _)| top + 1 == landing)
+            .map(|(_, j)| *j)
+            .collect();
+        for j in &supporters {
+            settled.supports[*j].push(i);
+        }
+        settled.supported_by[i] = supporters.into_iter().collect();
+
+        let rested = cuboid.lowered(cuboid.min.z - landing);
+        for column in rested.footprint() {
+            tops.insert(column, (rested.max.z, i));
+        }
+        settled.cuboids[i] = rested;
+    }
+    settled
+}
+
+impl Settled {
+    /// Whether every box resting on `i` has something else holding it up
+    pub fn is_removable(&self, i: usize) -> bool {
+        self.supports[i]
+            .iter()
+            .all(|j| self.supported_by[*j].len() > 1)
+    }
+
+    /// Every other box which falls if `i` is taken away, including those
+    /// which only fall because the boxes beneath them fell
+    pub fn falls_without(&self, i: usize) -> Vec<usize> {
+        let mut remaining: Vec<usize> = self.supported_by.iter().map(|s| s.len()).collect();
+        let mut falling = Vec::new();
+        let mut queue = VecDeque::from([i]);
+        while let Some(removed) = queue.pop_front() {
+            for j in &self.supports[removed] {
+                remaining[*j] -= 1;
+                if remaining[*j] == 0 {
+                    falling.push(*j);
+                    queue.push_back(*j);
+                }
+            }
+        }
+        falling.sort();
+        falling
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartesian::Vector;
+
+    use super::*;
+
+    fn rocks() -> Vec<Point> {
+        let paths: Vec<Vector> = vec![
+            ((498, 4).into(), (498, 6).into()).into(),
+            ((498, 6).into(), (496, 6).into()).into(),
+            ((503, 4).into(), (502, 4).into()).into(),
+            ((502, 4).into(), (502, 9).into()).into(),
+            ((502, 9).into(), (494, 9).into()).into(),
+        ];
+        paths.iter().flat_map(|v| v.points()).collect()
+    }
+
+    fn bricks() -> Vec<Cuboid> {
+        [
+            ((1, 0, 1), (1, 2, 1)),
+            ((0, 0, 2), (2, 0, 2)),
+            ((0, 2, 3), (2, 2, 3)),
+            ((0, 0, 4), (0, 2, 4)),
+            ((2, 0, 5), (2, 2, 5)),
+            ((0, 1, 6), (2, 1, 6)),
+            ((1, 1, 8), (1, 1, 9)),
+        ]
+        .into_iter()
+        .map(|(a, b)| Cuboid::new(a.into(), b.into()))
+        .collect()
+    }
+
+    #[test]
+    fn test_drop_single_grains() {
+        let mut pile = SandPile::new(rocks(), Floor::Abyss);
+        assert_eq!(Some(Point::new(500, 8)), pile.drop_from((500, 0).into()));
+        assert_eq!(Some(Point::new(499, 8)), pile.drop_from((500, 0).into()));
+        assert_eq!(Some(Point::new(501, 8)), pile.drop_from((500, 0).into()));
+        // nothing under this one
+        assert_eq!(None, pile.drop_from((510, 0).into()));
+        // nor somewhere already full
+        assert_eq!(None, pile.drop_from((500, 8).into()));
+        assert_eq!(3, pile.rested().len());
+    }
+
+    #[test]
+    fn test_fill_until_falling_forever() {
+        let mut pile = SandPile::new(rocks(), Floor::Abyss);
+        let rested = pile.fill((500, 0).into());
+        assert_eq!(24, rested.len());
+        assert_eq!(Point::new(500, 8), rested[0]);
+        assert_eq!(Point::new(495, 8), rested[23]);
+
+        // filling one grain at a time comes to the same places
+        let mut one_at_a_time = SandPile::new(rocks(), Floor::Abyss);
+        while one_at_a_time.drop_from((500, 0).into()).is_some() {}
+        assert_eq!(rested, one_at_a_time.rested());
+    }
+
+    #[test]
+    fn test_fill_onto_floor() {
+        let mut pile = SandPile::new(rocks(), Floor::Solid(11));
+        assert_eq!(93, pile.fill((500, 0).into()).len());
+        assert!(pile.is_blocked(&(500, 0).into()));
+        assert_eq!(None, pile.drop_from((500, 0).into()));
+        assert!(pile.is_blocked(&(-1000, 11).into()));
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let a = Cuboid::new((1, 2, 1).into(), (1, 0, 1).into());
+        assert_eq!(Point3::new(1, 0, 1), a.min);
+        assert!(a.overlaps(&a));
+        assert!(!a.lowered(1).overlaps(&a));
+        assert!(a.lowered(1).overlaps(&a.lowered(1)));
+        assert_eq!(3, a.footprint().count());
+    }
+
+    #[test]
+    fn test_settle() {
+        let settled = settle(&bricks(), 1);
+        let bottoms: Vec<i64> = settled.cuboids.iter().map(|c| c.min.z).collect();
+        assert_eq!(vec![1, 2, 2, 3, 3, 4, 5], bottoms);
+        assert_eq!(6, settled.cuboids[6].max.z);
+
+        assert_eq!(Vec::<usize>::new(), settled.supported_by[0]);
+        assert_eq!(vec![1, 2], settled.supports[0]);
+        assert_eq!(vec![1, 2], settled.supported_by[3]);
+        assert_eq!(vec![5], settled.supported_by[6]);
+    }
+
+    #[test]
+    fn test_what_falls() {
+        let settled = settle(&bricks(), 1);
+        let removable: Vec<usize> = (0..7).filter(|i| settled.is_removable(*i)).collect();
+        assert_eq!(vec![1, 2, 3, 4, 6], removable);
+
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], settled.falls_without(0));
+        assert_eq!(vec![6], settled.falls_without(5));
+        let total: usize = (0..7).map(|i| settled.falls_without(i).len()).sum();
+        assert_eq!(7, total);
+    }
+
+    #[test]
+    fn test_settle_order_independent() {
+        let mut reversed = bricks();
+        reversed.reverse();
+        let settled = settle(&reversed, 1);
+        assert_eq!(1, settled.cuboids[6].min.z);
+        assert_eq!(5, settled.cuboids[0].min.z);
+        assert_eq!(vec![1], settled.supported_by[0]);
+    }
+
+    #[test]
+    fn test_floor_holds_boxes_up() {
+        let settled = settle(&bricks(), 3);
+        assert_eq!(3, settled.cuboids[0].min.z);
+        assert!(settled.supported_by[0].is_empty());
+        assert_eq!(4, settled.cuboids[1].min.z);
+        assert_eq!(vec![0], settled.supported_by[1]);
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    env, fmt, fs, io,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The least time between two requests to the server, inputs are cached
+/// so this only slows down fetching several new days at once.
+pub const DEFAULT_RATE_LIMIT: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/oxlade39/aoc2021 aoclib input fetcher";
+
+/// Where inputs come from and where they are kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The `session` cookie of a logged in browser
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+    pub base_url: String,
+    pub rate_limit: Duration,
+}
+
+impl Config {
+    /// Reads the session token from `AOC_SESSION`, or the file `~/.config/aoc/session`.
+    ///
+    /// `AOC_CACHE_DIR` and `AOC_BASE_URL` override the default `~/.cache/aoc`
+    /// and the real site respectively.
+    pub fn from_env() -> Self {
+        let home = env::var_os("HOME").map(PathBuf::from);
+        let session = env::var("AOC_SESSION").ok().or_else(|| {
+            let file = home.as_ref()?.join(".config/aoc/session");
+            fs::read_to_string(file).ok()
+        });
+        let cache_dir = env::var_os("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .or_else(|| home.map(|h| h.join(".cache/aoc")))
+            .unwrap_or_else(|| env::temp_dir().join("aoc"));
+
+        Config {
+            session: session.map(|s| s.trim().to_owned()),
+            cache_dir,
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned()),
+            rate_limit: DEFAULT_RATE_LIMIT,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// There is no cached input and no session token to fetch it with
+    NoSession,
+    /// The server answered, but not with the input
+    Status(u16, String),
+    /// The server couldn't be reached and there is no cached input
+    Offline(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => {
+                write!(
+                    f,
+                    "no session token, set AOC_SESSION or ~/.config/aoc/session"
+                )
+            }
+            FetchError::Status(code, body) => write!(f, "server responded {code}: {body}"),
+            FetchError::Offline(reason) => write!(f, "offline with nothing cached: {reason}"),
+            FetchError::Io(e) => write!(f, "cache error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(value: io::Error) -> Self {
+        FetchError::Io(value)
+    }
+}
+
+/// Fetches puzzle inputs by year and day, keeping a copy of each in the cache.
+///
+/// ```no_run
+/// use aoclib::fetch::{Config, InputProvider};
+///
+/// let inputs = InputProvider::new(Config::from_env());
+/// let txt = inputs.input(2023, 1).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputProvider {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl InputProvider {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        InputProvider { config, agent }
+    }
+
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.config
+            .cache_dir
+            .join(year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+
+    /// The cached input if there is one, otherwise fetches and caches it.
+    /// Inputs never change so the server is only asked once.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        match fs::read_to_string(self.cache_path(year, day)) {
+            Ok(cached) => Ok(cached),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => self.fetch(year, day),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Fetches the input even if it is cached, falling back to the cache
+    /// if the server can't be reached.
+    pub fn refresh(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        match self.fetch(year, day) {
+            Err(FetchError::Offline(reason)) => fs::read_to_string(self.cache_path(year, day))
+                .map_err(|_| FetchError::Offline(reason)),
+            other => other,
+        }
+    }
+
+    fn fetch(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let session = self.config.session.as_ref().ok_or(FetchError::NoSession)?;
+        self.wait_for_rate_limit()?;
+
+        let url = format!("{}/{year}/day/{day}/input", self.config.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .call();
+
+        let body = match response {
+            Ok(r) => r.into_string()?,
+            Err(ureq::Error::Status(code, r)) => {
+                return Err(FetchError::Status(
+                    code,
+                    r.into_string().unwrap_or_default(),
+                ))
+            }
+            Err(ureq::Error::Transport(t)) => return Err(FetchError::Offline(t.to_string())),
+        };
+
+        let path = self.cache_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, &body)?;
+        Ok(body)
+    }
+
+    /// Sleeps until `rate_limit` has passed since the last request from any run,
+    /// which is remembered by touching a file in the cache.
+    fn wait_for_rate_limit(&self) -> Result<(), FetchError> {
+        fs::create_dir_all(&self.config.cache_dir)?;
+        let marker = self.config.cache_dir.join(".last-request");
+        let since_last = fs::metadata(&marker)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| SystemTime::now().duration_since(t).ok());
+        if let Some(since) = since_last {
+            if since < self.config.rate_limit {
+                thread::sleep(self.config.rate_limit - since);
+            }
+        }
+        fs::write(marker, [])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Instant,
+    };
+
+    use super::*;
+
+    struct Request {
+        line: String,
+        cookie: Option<String>,
+    }
+
+    /// A stand-in for the puzzle server, answering every request with `status` and `body`
+    /// and recording the requests it was sent
+    struct MockServer {
+        url: String,
+        requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl MockServer {
+        fn start(status: u16, body: &'static str) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = requests.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut cookie = None;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        if header.trim().is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = header.split_once(':') {
+                            if name.eq_ignore_ascii_case("cookie") {
+                                cookie = Some(value.trim().to_owned());
+                            }
+                        }
+                    }
+                    recorded.lock().unwrap().push(Request {
+                        line: request_line.trim().to_owned(),
+                        cookie,
+                    });
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            });
+            MockServer { url, requests }
+        }
+
+        fn request_count(&self) -> usize {
+            self.requests.lock().unwrap().len()
+        }
+    }
+
+    /// A cache directory of its own for each test, removed when the test ends
+    struct TempCache(PathBuf);
+
+    impl TempCache {
+        fn new() -> Self {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let dir = env::temp_dir().join(format!(
+                "aoclib-fetch-{}-{}",
+                std::process::id(),
+                COUNT.fetch_add(1, Ordering::SeqCst)
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            TempCache(dir)
+        }
+    }
+
+    impl Drop for TempCache {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn config(base_url: &str) -> (Config, TempCache) {
+        let cache = TempCache::new();
+        let config = Config {
+            session: Some("abc123".to_owned()),
+            cache_dir: cache.0.clone(),
+            base_url: base_url.to_owned(),
+            rate_limit: Duration::ZERO,
+        };
+        (config, cache)
+    }
+
+    #[test]
+    fn test_fetches_then_caches() {
+        let server = MockServer::start(200, "1\n2\n3\n");
+        let (config, _cache) = config(&server.url);
+        let provider = InputProvider::new(config);
+
+        assert_eq!("1\n2\n3\n", provider.input(2023, 7).unwrap());
+        assert_eq!("1\n2\n3\n", provider.input(2023, 7).unwrap());
+        assert_eq!(1, server.request_count());
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!("GET /2023/day/7/input HTTP/1.1", requests[0].line);
+        assert_eq!(Some("session=abc123".to_owned()), requests[0].cookie);
+        assert_eq!(
+            "1\n2\n3\n",
+            fs::read_to_string(provider.cache_path(2023, 7)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_error_status_is_not_cached() {
+        let server = MockServer::start(404, "not unlocked yet");
+        let (config, _cache) = config(&server.url);
+        let provider = InputProvider::new(config);
+
+        match provider.input(2023, 25) {
+            Err(FetchError::Status(404, body)) => assert_eq!("not unlocked yet", body),
+            other => panic!("expected a 404 but got {other:?}"),
+        }
+        assert!(!provider.cache_path(2023, 25).exists());
+    }
+
+    #[test]
+    fn test_no_session() {
+        let (config, _cache) = config("http://127.0.0.1:1");
+        let provider = InputProvider::new(Config {
+            session: None,
+            ..config
+        });
+        assert!(matches!(
+            provider.input(2023, 1),
+            Err(FetchError::NoSession)
+        ));
+    }
+
+    #[test]
+    fn test_offline_falls_back_to_cache() {
+        // nothing listens on a port once its listener is dropped
+        let closed = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let (config, _cache) = config(&closed);
+        let provider = InputProvider::new(config);
+
+        assert!(matches!(
+            provider.refresh(2022, 3),
+            Err(FetchError::Offline(_))
+        ));
+
+        let path = provider.cache_path(2022, 3);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "cached").unwrap();
+        assert_eq!("cached", provider.refresh(2022, 3).unwrap());
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let server = MockServer::start(200, "input");
+        let (config, _cache) = config(&server.url);
+        let provider = InputProvider::new(Config {
+            rate_limit: Duration::from_millis(300),
+            ..config
+        });
+
+        let start = Instant::now();
+        provider.refresh(2021, 1).unwrap();
+        provider.refresh(2021, 1).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(2, server.request_count());
+    }
+}
@@ -187,6 +187,32 @@ pub trait FromChar: Sized {
     fn from_char(c: char) -> Result<Self, Self::Err>;
 }
 
+/// The char a tile is written as, the reverse of [`FromChar`]
+pub trait ToChar {
+    fn to_char(&self) -> char;
+}
+
+/// Tile enums can derive both directions from one `#[tile(...)]` per variant,
+/// the first char is the one written by [`ToChar`] (which also derives `Display`).
+///
+/// ```
+/// use aoclib::grid::{FromChar, Grid, ToChar};
+///
+/// #[derive(Debug, PartialEq, FromChar, ToChar)]
+/// enum Tile {
+///     #[tile('.', 'S')]
+///     Garden,
+///     #[tile('#')]
+///     Rock,
+/// }
+///
+/// let g: Grid<Tile> = "S#\n..".parse().unwrap();
+/// assert_eq!(Tile::Garden, g.rows[0][0]);
+/// assert_eq!('#', g.rows[0][1].to_char());
+/// assert_eq!(vec![".#", ".."], g.to_string().lines().collect::<Vec<_>>());
+/// ```
+pub use aoclib_derive::{FromChar, ToChar};
+
 impl FromChar for char {
     type Err = String;
 
@@ -217,18 +243,106 @@ impl FromChar for usize {
     }
 }
 
-impl<T> FromStr for Grid<T>
+/// Why some text couldn't be parsed into a [`Grid`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E> {
+    /// A character [`FromChar`] rejected
+    BadChar {
+        position: GridPosition,
+        c: char,
+        err: E,
+    },
+    /// A row whose width differs from the first row
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl<E> fmt::Display for GridParseError<E>
 where
-    T: FromChar,
+    E: fmt::Display,
 {
-    type Err = String;
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridParseError::BadChar { position, c, err } => write!(
+                f,
+                "bad char {c:?} at row {} col {}: {err}",
+                position.row, position.col
+            ),
+            GridParseError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(f, "row {row} has width {width} but expected {expected}"),
+        }
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl<E> std::error::Error for GridParseError<E> where E: fmt::Debug + fmt::Display {}
+
+impl<E> From<GridParseError<E>> for String
+where
+    E: fmt::Display,
+{
+    fn from(value: GridParseError<E>) -> Self {
+        value.to_string()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: FromChar,
+{
+    /// Parses whatever characters it can, silently dropping those `T` rejects
+    /// and allowing rows of different widths.
+    ///
+    /// Prefer `str::parse`, which reports those problems.
+    pub fn parse_lenient(s: &str) -> Self {
         let rows = s
+            .trim_end_matches(['\r', '\n'])
             .lines()
             .map(|l| l.chars().filter_map(|c| T::from_char(c).ok()).collect())
             .collect();
 
+        Grid { rows }
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: FromChar,
+{
+    type Err = GridParseError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for (row, line) in s.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let parsed = line
+                .chars()
+                .enumerate()
+                .map(|(col, c)| {
+                    T::from_char(c).map_err(|err| GridParseError::BadChar {
+                        position: GridPosition::new(col, row),
+                        c,
+                        err,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(first) = rows.first() {
+                if first.len() != parsed.len() {
+                    return Err(GridParseError::Ragged {
+                        row,
+                        width: parsed.len(),
+                        expected: first.len(),
+                    });
+                }
+            }
+            rows.push(parsed);
+        }
+
         Ok(Grid { rows })
     }
 }
@@ -262,7 +376,10 @@ impl Flip for u32 {
 #[cfg(test)]
 mod tests {
 
-    use crate::{grid::Grid, input::*};
+    use crate::{
+        grid::{FromChar, Grid, GridParseError, GridPosition, ToChar},
+        input::*,
+    };
 
     use super::Flip;
 
@@ -274,6 +391,83 @@ mod tests {
         assert_eq!(vec!["first", "second", "third"], chunks);
     }
 
+    #[test]
+    fn test_parse_any_line_ending() {
+        let unix: Grid<char> = "ab\ncd\n\n".parse().unwrap();
+        let windows: Grid<char> = "ab\r\ncd\r\n\r\n".parse().unwrap();
+        let expected = Grid {
+            rows: vec![vec!['a', 'b'], vec!['c', 'd']],
+        };
+        assert_eq!(expected, unix);
+        assert_eq!(expected, windows);
+    }
+
+    #[test]
+    fn test_parse_reports_bad_char() {
+        let result = "12\n3x".parse::<Grid<u32>>();
+        assert_eq!(
+            Err(GridParseError::BadChar {
+                position: GridPosition::new(1, 1),
+                c: 'x',
+                err: "bad digit x".to_owned()
+            }),
+            result
+        );
+        assert_eq!(
+            "bad char 'x' at row 1 col 1: bad digit x",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_ragged_rows() {
+        let result = "123\n45\n678".parse::<Grid<u32>>();
+        assert_eq!(
+            Err(GridParseError::Ragged {
+                row: 1,
+                width: 2,
+                expected: 3
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let g: Grid<u32> = Grid::parse_lenient("1x2\n34");
+        assert_eq!(
+            Grid {
+                rows: vec![vec![1, 2], vec![3, 4]]
+            },
+            g
+        );
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromChar, ToChar)]
+    enum Mirror {
+        #[tile('.')]
+        Space,
+        #[tile('/')]
+        Right,
+        #[tile('\\')]
+        Left,
+    }
+
+    #[test]
+    fn test_derived_tiles() {
+        assert_eq!(Ok(Mirror::Left), Mirror::from_char('\\'));
+        assert_eq!(
+            Err("no Mirror tile for 'x'".to_owned()),
+            Mirror::from_char('x')
+        );
+        assert_eq!('/', Mirror::Right.to_char());
+
+        let txt = "./\\\n\\..";
+        let g: Grid<Mirror> = txt.parse().unwrap();
+        assert_eq!(vec![Mirror::Space, Mirror::Right, Mirror::Left], g.rows[0]);
+        assert_eq!(txt, g.to_string().lines().collect::<Vec<_>>().join("\n"));
+    }
+
     #[test]
     fn test_transpose() {
         let g = Grid {
@@ -1,3 +1,5 @@
+use std::{any::type_name, error::Error, fmt, str::FromStr};
+
 #[cfg(windows)]
 pub const NEW_LINE: &'static str = "\r\n";
 
@@ -10,8 +12,425 @@ pub const EMPTY_LINE: &'static str = "\r\n\r\n";
 #[cfg(not(windows))]
 pub const EMPTY_LINE: &'static str = "\n\n";
 
-pub fn empty_line_chunks<'a>(input: &'a str) -> std::str::Split<'a, &'a str> {
-    input.split(EMPTY_LINE)
+/// Splits `input` into the paragraphs between blank lines, whichever line endings it uses.
+///
+/// Runs of blank lines, including any at the start or end, never produce empty chunks.
+pub fn empty_line_chunks(input: &str) -> impl Iterator<Item = &str> {
+    Paragraphs {
+        rest: input,
+        line: 1,
+    }
+    .map(|(_, paragraph)| paragraph)
+}
+
+/// The next line without its `\n` or `\r\n`, and whatever follows it
+fn split_line(s: &str) -> (&str, &str) {
+    let (line, rest) = s.split_once('\n').unwrap_or((s, ""));
+    (line.strip_suffix('\r').unwrap_or(line), rest)
+}
+
+/// Paragraphs along with the line number they start on
+struct Paragraphs<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (line, after) = split_line(self.rest);
+            if !line.is_empty() {
+                break;
+            }
+            self.rest = after;
+            self.line += 1;
+        }
+
+        let start = self.rest;
+        let first_line = self.line;
+        let mut end = 0;
+        while !self.rest.is_empty() {
+            let (line, after) = split_line(self.rest);
+            if line.is_empty() {
+                break;
+            }
+            end = start.len() - self.rest.len() + line.len();
+            self.rest = after;
+            self.line += 1;
+        }
+        Some((first_line, &start[..end]))
+    }
+}
+
+/// Where and why some input couldn't be parsed.
+///
+/// Lines and columns count from 1, the line is only known once
+/// the error has passed through something that splits lines such as [`parse_lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn at(column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line: None,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        ParseError {
+            line: Some(line),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}, column {}: {}", self.column, self.message),
+            None => write!(f, "column {}: {}", self.column, self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for String {
+    fn from(value: ParseError) -> Self {
+        value.to_string()
+    }
+}
+
+/// A scanf like pattern of literal text with a `{}` placeholder for each value to capture.
+///
+/// Each capture runs up to the next occurrence of the literal text after it,
+/// a capture at the end of the pattern takes the rest of the input.
+///
+/// ```
+/// use aoclib::input::Pattern;
+///
+/// let node = Pattern::new("{} = ({}, {})");
+/// let captures = node.captures("AAA = (BBB, CCC)").unwrap();
+/// assert_eq!(vec!["AAA", "BBB", "CCC"], captures.values());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern<'p> {
+    // always one more literal than there are captures
+    literals: Vec<&'p str>,
+}
+
+impl<'p> Pattern<'p> {
+    pub fn new(pattern: &'p str) -> Self {
+        let literals: Vec<_> = pattern.split("{}").collect();
+        let between = literals.get(1..literals.len().saturating_sub(1));
+        if between.is_some_and(|between| between.iter().any(|l| l.is_empty())) {
+            panic!("captures in {pattern:?} must be separated by some text");
+        }
+        Pattern { literals }
+    }
+
+    pub fn capture_count(&self) -> usize {
+        self.literals.len() - 1
+    }
+
+    pub fn captures<'a>(&self, input: &'a str) -> Result<Captures<'a>, ParseError> {
+        let column = |pos: usize| input[..pos].chars().count() + 1;
+        let expected = |pos: usize, literal: &str| {
+            let found = match &input[pos..] {
+                "" => "the end".to_owned(),
+                rest => format!("{rest:?}"),
+            };
+            ParseError::at(
+                column(pos),
+                format!("expected {literal:?} but found {found}"),
+            )
+        };
+
+        let first = self.literals[0];
+        if !input.starts_with(first) {
+            let matched = common_prefix(input, first);
+            return Err(expected(matched, &first[matched..]));
+        }
+        let mut pos = first.len();
+        let mut values = Vec::with_capacity(self.capture_count());
+
+        for &literal in &self.literals[1..] {
+            let length = if literal.is_empty() {
+                input.len() - pos
+            } else {
+                input[pos..].find(literal).ok_or_else(|| {
+                    let (at, matched) = mismatch(input, pos, literal);
+                    expected(at, &literal[matched..])
+                })?
+            };
+            if length == 0 {
+                return Err(ParseError::at(column(pos), "expected a value"));
+            }
+            values.push((column(pos), &input[pos..pos + length]));
+            pos += length + literal.len();
+        }
+
+        if pos != input.len() {
+            return Err(ParseError::at(
+                column(pos),
+                format!("unexpected {:?} after the pattern", &input[pos..]),
+            ));
+        }
+
+        Ok(Captures { values, next: 0 })
+    }
+}
+
+/// How many bytes `a` and `b` start with in common
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x == y)
+        .map(|(x, _)| x.len_utf8())
+        .sum()
+}
+
+/// Where matching `literal` after the capture starting at `pos` broke down,
+/// just past the longest start of `literal` found after at least one captured character,
+/// along with how much of `literal` had matched there
+fn mismatch(input: &str, pos: usize, literal: &str) -> (usize, usize) {
+    let rest = &input[pos..];
+    let after = pos + rest.chars().next().map_or(0, char::len_utf8);
+    (1..literal.len())
+        .rev()
+        .filter(|n| literal.is_char_boundary(*n))
+        .find_map(|n| {
+            input[after..]
+                .find(&literal[..n])
+                .map(|i| (after + i + n, n))
+        })
+        .unwrap_or((input.len(), 0))
+}
+
+/// The text captured by a [`Pattern`], taken in order with [`Captures::parse_next`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'a> {
+    values: Vec<(usize, &'a str)>,
+    next: usize,
+}
+
+impl<'a> Captures<'a> {
+    pub fn values(&self) -> Vec<&'a str> {
+        self.values.iter().map(|(_, v)| *v).collect()
+    }
+
+    /// Parses the next capture, failing at its column if it isn't a `T`
+    pub fn parse_next<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let Some(&(column, value)) = self.values.get(self.next) else {
+            return Err(ParseError::at(
+                1,
+                format!("only {} values were captured", self.values.len()),
+            ));
+        };
+        self.next += 1;
+        value.parse().map_err(|e| {
+            ParseError::at(
+                column,
+                format!("{value:?} is not a {}: {e}", type_name::<T>()),
+            )
+        })
+    }
+
+    /// Fails if any captures were never parsed
+    pub fn finish(&self) -> Result<(), ParseError> {
+        match self.values.get(self.next) {
+            Some(&(column, value)) => Err(ParseError::at(
+                column,
+                format!("captured {value:?} but it was never used"),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Matches `input` against a [`Pattern`], parsing each capture as the given type.
+///
+/// ```
+/// use aoclib::scan;
+///
+/// let (axis, start, end) = scan!("{}={}..{}", "x=-20..26", char, i64, i64).unwrap();
+/// assert_eq!(('x', -20, 26), (axis, start, end));
+///
+/// let err = scan!("{}={}..{}", "x=-20..2b", char, i64, i64).unwrap_err();
+/// assert_eq!(8, err.column);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($pattern:expr, $input:expr, $($t:ty),+ $(,)?) => {
+        $crate::input::Pattern::new($pattern)
+            .captures($input)
+            .and_then(|mut captures| {
+                let values = ($(captures.parse_next::<$t>()?,)+);
+                captures.finish()?;
+                Ok(values)
+            })
+    };
+}
+
+/// Implements `FromStr` for a record from an `#[input("...")]` pattern naming its fields.
+///
+/// ```
+/// use aoclib::input::FromInput;
+///
+/// #[derive(Debug, PartialEq, FromInput)]
+/// #[input("move {count} from {from} to {to}")]
+/// struct Move {
+///     count: usize,
+///     from: usize,
+///     to: usize,
+/// }
+///
+/// let m: Move = "move 3 from 1 to 2".parse().unwrap();
+/// assert_eq!(Move { count: 3, from: 1, to: 2 }, m);
+///
+/// let err = "move 3 from x to 2".parse::<Move>().unwrap_err();
+/// assert_eq!(13, err.column);
+/// ```
+pub use aoclib_derive::FromInput;
+
+// used by code generated for `FromInput`
+#[doc(hidden)]
+pub use regex;
+
+/// Parses the capture group `name` as a `T`, for code generated by [`FromInput`]
+#[doc(hidden)]
+pub fn parse_field<T>(captures: &regex::Captures, name: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let m = captures
+        .name(name)
+        .ok_or_else(|| ParseError::at(1, format!("nothing captured for {name}")))?;
+    let column = captures[0][..m.start()].chars().count() + 1;
+    m.as_str().parse().map_err(|e| {
+        ParseError::at(
+            column,
+            format!("{name} {:?} is not a {}: {e}", m.as_str(), type_name::<T>()),
+        )
+    })
+}
+
+/// The error for `s` not matching `pattern`, for code generated by [`FromInput`].
+///
+/// Points just past the furthest of `prefixes`, the regexes for each part of the
+/// pattern up to the end of a literal, which `s` does match.
+#[doc(hidden)]
+pub fn pattern_mismatch(s: &str, pattern: &str, prefixes: &[&str]) -> ParseError {
+    let matched = prefixes
+        .iter()
+        .rev()
+        .find_map(|prefix| regex::Regex::new(prefix).ok()?.find(s))
+        .map_or(0, |m| m.end());
+    ParseError::at(
+        s[..matched].chars().count() + 1,
+        format!("{s:?} doesn't match {pattern:?}"),
+    )
+}
+
+/// Parses every line with `parse`, adding the line number to any error
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Parses every line as a `T`, ignoring blank lines at the end
+pub fn lines_of<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse_lines(input.trim_end_matches(['\r', '\n']), |line| {
+        line.parse()
+            .map_err(|e| ParseError::at(1, format!("{line:?} is not a {}: {e}", type_name::<T>())))
+    })
+}
+
+/// Parses every paragraph between blank lines as a `T`,
+/// errors are reported on the line the paragraph starts
+pub fn paragraphs_of<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Paragraphs {
+        rest: input,
+        line: 1,
+    }
+    .map(|(line, paragraph)| {
+        paragraph.parse().map_err(|e| {
+            ParseError::at(1, format!("paragraph is not a {}: {e}", type_name::<T>())).on_line(line)
+        })
+    })
+    .collect()
+}
+
+/// Every integer in `input`, skipping any other text.
+///
+/// A `-` is only a sign when it doesn't follow a digit, so ranges like `2-4` are positive.
+///
+/// ```
+/// use aoclib::input::ints;
+///
+/// let sensor = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+/// assert_eq!(Ok(vec![2, -18, -2, 15]), ints(sensor));
+/// assert_eq!(Ok(vec![2, 4, 6, 8]), ints("2-4,6-8"));
+/// ```
+pub fn ints(input: &str) -> Result<Vec<i64>, ParseError> {
+    let mut found = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let bytes = line.as_bytes();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let negative = bytes[pos] == b'-'
+                && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit)
+                && (pos == 0 || !bytes[pos - 1].is_ascii_digit());
+            if !negative && !bytes[pos].is_ascii_digit() {
+                pos += 1;
+                continue;
+            }
+            let start = pos;
+            pos += 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            let value = line[start..pos].parse().map_err(|e| {
+                ParseError::at(
+                    start + 1,
+                    format!("{:?} is not an i64: {e}", &line[start..pos]),
+                )
+                .on_line(i + 1)
+            })?;
+            found.push(value);
+        }
+    }
+    Ok(found)
 }
 
 #[cfg(test)]
@@ -26,4 +445,247 @@ mod tests {
         let chunks: Vec<_> = empty_line_chunks(text).collect();
         assert_eq!(vec!["first", "second", "third"], chunks);
     }
+
+    #[test]
+    fn test_empty_line_chunks_any_line_ending() {
+        let crlf = "a\r\nb\r\n\r\nc\r\n";
+        assert_eq!(
+            vec!["a\r\nb", "c"],
+            empty_line_chunks(crlf).collect::<Vec<_>>()
+        );
+
+        let untidy = "\n\na\nb\n\n\n\nc\n\n\n";
+        assert_eq!(
+            vec!["a\nb", "c"],
+            empty_line_chunks(untidy).collect::<Vec<_>>()
+        );
+
+        let mixed = "a\r\n\nb";
+        assert_eq!(vec!["a", "b"], empty_line_chunks(mixed).collect::<Vec<_>>());
+
+        assert_eq!(0, empty_line_chunks("").count());
+        assert_eq!(0, empty_line_chunks("\r\n\r\n").count());
+    }
+
+    #[test]
+    fn test_lines_of() {
+        assert_eq!(Ok(vec![1, 2, 3]), lines_of::<u8>("1\r\n2\r\n3\r\n\r\n"));
+
+        let err = lines_of::<u8>("1\n2\nx\n").unwrap_err();
+        assert_eq!(Some(3), err.line);
+        assert!(err.message.starts_with(r#""x" is not a u8"#));
+    }
+
+    #[test]
+    fn test_paragraphs_of() {
+        #[derive(Debug, PartialEq)]
+        struct Elf(u32);
+
+        impl FromStr for Elf {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let calories: Result<Vec<u32>, _> = s.lines().map(|l| l.parse::<u32>()).collect();
+                Ok(Elf(calories.map_err(|e| e.to_string())?.iter().sum()))
+            }
+        }
+
+        let txt = "1000\r\n2000\r\n\r\n4000\r\n\r\n";
+        assert_eq!(Ok(vec![Elf(3000), Elf(4000)]), paragraphs_of(txt));
+
+        let err = paragraphs_of::<Elf>("1\n\n\n2\nx").unwrap_err();
+        assert_eq!(Some(4), err.line);
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(Ok(vec![-20, 26, -36, 17]), ints("on x=-20..26,y=-36..17"));
+        assert_eq!(Ok(vec![3, 1, 2]), ints("move 3 from 1 to 2"));
+        assert_eq!(Ok(vec![1, 2, 3, 4]), ints("1 2\r\n3 4\r\n"));
+        assert_eq!(Ok(vec![]), ints("no numbers - here"));
+
+        let err = ints("1\nvalue 99999999999999999999").unwrap_err();
+        assert_eq!(Some(2), err.line);
+        assert_eq!(7, err.column);
+    }
+
+    #[test]
+    fn test_pattern_captures() {
+        let p = Pattern::new("{} = ({}, {})");
+        assert_eq!(3, p.capture_count());
+        let c = p.captures("AAA = (BBB, CCC)").unwrap();
+        assert_eq!(vec!["AAA", "BBB", "CCC"], c.values());
+
+        let p = Pattern::new("Monkey {}:");
+        assert_eq!(vec!["12"], p.captures("Monkey 12:").unwrap().values());
+
+        let p = Pattern::new("move {} from {} to {}");
+        assert_eq!(
+            vec!["3", "1", "2"],
+            p.captures("move 3 from 1 to 2").unwrap().values()
+        );
+    }
+
+    #[test]
+    fn test_pattern_errors() {
+        let p = Pattern::new("{} = ({}, {})");
+        let err = p.captures("AAA = BBB, CCC)").unwrap_err();
+        assert_eq!(7, err.column);
+        assert_eq!(r#"expected "(" but found "BBB, CCC)""#, err.message);
+
+        let err = p.captures("AAA = (BBB; CCC)").unwrap_err();
+        assert_eq!(17, err.column);
+        assert_eq!(r#"expected ", " but found the end"#, err.message);
+
+        let err = p.captures("AAA = (BBB, CCC) ").unwrap_err();
+        assert_eq!(17, err.column);
+
+        let err = p.captures("AAA = (, CCC)").unwrap_err();
+        assert_eq!(ParseError::at(8, "expected a value"), err);
+
+        let err = Pattern::new("Monkey {}:")
+            .captures("monkey 1:")
+            .unwrap_err();
+        assert_eq!(1, err.column);
+
+        let err = Pattern::new("Monkey {}:")
+            .captures("Monkey: 1:")
+            .unwrap_err();
+        assert_eq!(ParseError::at(7, r#"expected " " but found ": 1:""#), err);
+    }
+
+    #[test]
+    fn test_pattern_without_captures() {
+        let p = Pattern::new("no captures");
+        assert_eq!(0, p.capture_count());
+        assert!(p.captures("no captures").unwrap().values().is_empty());
+
+        let err = p.captures("no capture").unwrap_err();
+        assert_eq!(ParseError::at(11, r#"expected "s" but found the end"#), err);
+        let err = p.captures("no captures!").unwrap_err();
+        assert_eq!(12, err.column);
+    }
+
+    #[test]
+    fn test_scan_typed() {
+        let (name, left, right) =
+            crate::scan!("{} = ({}, {})", "AAA = (BBB, CCC)", String, String, String).unwrap();
+        assert_eq!(("AAA", "BBB", "CCC"), (&name[..], &left[..], &right[..]));
+
+        let (on, x1, x2) = crate::scan!("{} x={}..{}", "on x=-20..26", String, i64, i64).unwrap();
+        assert_eq!(("on".to_owned(), -20, 26), (on, x1, x2));
+
+        let err = crate::scan!("{},{}", "1,x", i64, i64).unwrap_err();
+        assert_eq!(3, err.column);
+        assert!(err.message.starts_with(r#""x" is not a i64"#));
+
+        // more captures than types
+        let err = crate::scan!("{},{}", "1,2", i64).unwrap_err();
+        assert_eq!(
+            ParseError::at(3, r#"captured "2" but it was never used"#),
+            err
+        );
+    }
+
+    #[derive(Debug, PartialEq, FromInput)]
+    #[input("{name}{{{rating:[xmas]}>{value}:{next}}}")]
+    struct Rule {
+        name: String,
+        rating: char,
+        value: u32,
+        next: String,
+    }
+
+    #[derive(Debug, PartialEq, FromInput)]
+    #[input("target area: x={x1}..{x2}, y={y1}..{y2}")]
+    struct Target {
+        x1: i64,
+        x2: i64,
+        y1: i64,
+        y2: i64,
+    }
+
+    #[test]
+    fn test_derive_from_input() {
+        let t: Target = "target area: x=20..30, y=-10..-5".parse().unwrap();
+        assert_eq!(
+            Target {
+                x1: 20,
+                x2: 30,
+                y1: -10,
+                y2: -5
+            },
+            t
+        );
+
+        // points past the furthest literal which matched
+        let err = "target area: x=20..30, y=-10"
+            .parse::<Target>()
+            .unwrap_err();
+        assert_eq!(26, err.column);
+        assert_eq!(
+            r#""target area: x=20..30, y=-10" doesn't match "target area: x={x1}..{x2}, y={y1}..{y2}""#,
+            err.message
+        );
+        let err = "target: x=20..30".parse::<Target>().unwrap_err();
+        assert_eq!(1, err.column);
+
+        let err = "target area: x=20..3O, y=-10..-5"
+            .parse::<Target>()
+            .unwrap_err();
+        assert_eq!(20, err.column);
+        assert!(err.message.starts_with(r#"x2 "3O" is not a i64"#));
+    }
+
+    #[test]
+    fn test_derive_from_input_custom_group() {
+        let r: Rule = "px{a>2006:qkq}".parse().unwrap();
+        assert_eq!(
+            Rule {
+                name: "px".to_owned(),
+                rating: 'a',
+                value: 2006,
+                next: "qkq".to_owned()
+            },
+            r
+        );
+        assert!("px{q>2006:qkq}".parse::<Rule>().is_err());
+    }
+
+    #[derive(Debug, PartialEq, FromInput)]
+    #[input("{code:[A-Z]{2}}-{n:\\d{3}}")]
+    struct Code {
+        code: String,
+        n: u32,
+    }
+
+    #[test]
+    fn test_derive_from_input_quantified_group() {
+        let c: Code = "AB-042".parse().unwrap();
+        assert_eq!(
+            Code {
+                code: "AB".to_owned(),
+                n: 42
+            },
+            c
+        );
+        assert_eq!(4, "AB-42".parse::<Code>().unwrap_err().column);
+        assert_eq!(1, "ABC-042".parse::<Code>().unwrap_err().column);
+    }
+
+    #[test]
+    fn test_parse_lines_adds_line_number() {
+        let parse = |l: &str| crate::scan!("{}-{}", l, u32, u32);
+        assert_eq!(Ok(vec![(1, 2), (3, 4)]), parse_lines("1-2\n3-4", parse));
+
+        let err = parse_lines("1-2\n3-4\n5_6", parse).unwrap_err();
+        assert_eq!(Some(3), err.line);
+        assert_eq!(4, err.column);
+        assert_eq!(
+            r#"line 3, column 4: expected "-" but found the end"#,
+            err.to_string()
+        );
+        let as_string: String = err.into();
+        assert!(as_string.starts_with("line 3"));
+    }
 }
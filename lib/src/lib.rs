@@ -1,8 +1,25 @@
+// lets derived code name `::aoclib` from within this crate too
+extern crate self as aoclib;
+
+pub mod automaton;
+pub mod beam;
 pub mod cartesian;
+pub mod circuit;
+pub mod cycle;
+pub mod diagram;
+pub mod diamond;
 pub mod distance;
+pub mod falling;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 pub mod grid;
 pub mod input;
+pub mod matrix;
 pub mod neighbour;
 pub mod number;
+pub mod polynomial;
 pub mod range;
+pub mod rope;
 pub mod shortest_path;
+pub mod tilt;
+pub mod workflow;
@@ -0,0 +1,525 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::number::{BigInt, Rational, SignedNumber};
+
+/// Values a [`Matrix`] can hold.
+///
+/// Division only needs to be exact when it is known to divide evenly,
+/// so integers work as well as [`Rational`]s.
+pub trait Element:
+    Clone
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+}
+
+macro_rules! integer_element {
+    ($($t:ty),*) => {
+        $(
+            impl Element for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+            }
+        )*
+    };
+}
+
+integer_element!(i32, i64, i128);
+
+impl Element for BigInt {
+    fn zero() -> Self {
+        BigInt::zero()
+    }
+
+    fn one() -> Self {
+        BigInt::from(1)
+    }
+}
+
+impl<T> Element for Rational<T>
+where
+    T: SignedNumber,
+{
+    fn zero() -> Self {
+        Rational::integer(0.into())
+    }
+
+    fn one() -> Self {
+        Rational::integer(1.into())
+    }
+}
+
+/// A rectangular matrix of values, stored row by row.
+///
+/// ```
+/// use aoclib::{matrix::Matrix, number::Rational};
+///
+/// // 2x + y = 5
+/// //  x - y = 1
+/// let a: Matrix<Rational> = Matrix::from(vec![vec![2, 1], vec![1, -1]]);
+/// let b = vec![5.into(), 1.into()];
+/// assert_eq!(Some(vec![Rational::from(2), Rational::from(1)]), a.solve(&b));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T> Matrix<T> {
+    pub fn new(rows: Vec<Vec<T>>) -> Self {
+        if let Some(first) = rows.first() {
+            let width = first.len();
+            if let Some(bad) = rows.iter().position(|r| r.len() != width) {
+                panic!(
+                    "row {bad} has {} columns but expected {width}",
+                    rows[bad].len()
+                );
+            }
+        }
+        Matrix { rows }
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |r| r.len())
+    }
+
+    pub fn rows(&self) -> &[Vec<T>] {
+        &self.rows
+    }
+
+    pub fn at(&self, row: usize, col: usize) -> &T {
+        &self.rows[row][col]
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.height() == self.width()
+    }
+
+    /// A new matrix with `f` applied to every value
+    pub fn map<U, F>(&self, f: F) -> Matrix<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Matrix {
+            rows: self
+                .rows
+                .iter()
+                .map(|r| r.iter().map(&f).collect())
+                .collect(),
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Element,
+{
+    pub fn identity(size: usize) -> Self {
+        Matrix {
+            rows: (0..size)
+                .map(|r| {
+                    (0..size)
+                        .map(|c| if r == c { T::one() } else { T::zero() })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Matrix {
+            rows: (0..self.width())
+                .map(|c| self.rows.iter().map(|r| r[c].clone()).collect())
+                .collect(),
+        }
+    }
+
+    /// Multiplies this matrix by the column vector `v`
+    pub fn apply(&self, v: &[T]) -> Vec<T> {
+        if v.len() != self.width() {
+            panic!(
+                "vector of {} can't multiply width {}",
+                v.len(),
+                self.width()
+            );
+        }
+        self.rows
+            .iter()
+            .map(|r| {
+                r.iter()
+                    .zip(v)
+                    .fold(T::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+            })
+            .collect()
+    }
+
+    /// The determinant using the fraction free
+    /// [Bareiss algorithm](https://en.wikipedia.org/wiki/Bareiss_algorithm),
+    /// so integer matrices never need a fraction along the way.
+    pub fn determinant(&self) -> T {
+        if !self.is_square() {
+            panic!(
+                "determinant needs a square matrix not {}x{}",
+                self.height(),
+                self.width()
+            );
+        }
+        let n = self.height();
+        let mut m = self.rows.clone();
+        let mut sign = T::one();
+        let mut previous = T::one();
+        for k in 0..n {
+            if m[k][k] == T::zero() {
+                match (k + 1..n).find(|&r| m[r][k] != T::zero()) {
+                    Some(swap) => {
+                        m.swap(k, swap);
+                        sign = -sign;
+                    }
+                    None => return T::zero(),
+                }
+            }
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    m[i][j] = (m[i][j].clone() * m[k][k].clone()
+                        - m[i][k].clone() * m[k][j].clone())
+                        / previous.clone();
+                }
+            }
+            previous = m[k][k].clone();
+        }
+        match n {
+            0 => T::one(),
+            _ => sign * m[n - 1][n - 1].clone(),
+        }
+    }
+}
+
+impl<T> Matrix<Rational<T>>
+where
+    T: SignedNumber,
+{
+    /// Reduces `[self | other]` to reduced row echelon form by Gauss-Jordan elimination,
+    /// returning the rank of `self` along with the transformed `other`.
+    fn eliminate(&self, other: &Matrix<Rational<T>>) -> (usize, Matrix<Rational<T>>) {
+        let zero = Rational::<T>::zero();
+        let mut left = self.rows.clone();
+        let mut right = other.rows.clone();
+        let mut rank = 0;
+        for col in 0..self.width() {
+            let Some(pivot) = (rank..left.len()).find(|&r| left[r][col] != zero) else {
+                continue;
+            };
+            left.swap(rank, pivot);
+            right.swap(rank, pivot);
+
+            let scale = left[rank][col].clone();
+            left[rank]
+                .iter_mut()
+                .for_each(|v| *v = v.clone() / scale.clone());
+            right[rank]
+                .iter_mut()
+                .for_each(|v| *v = v.clone() / scale.clone());
+
+            for r in 0..left.len() {
+                if r == rank || left[r][col] == zero {
+                    continue;
+                }
+                let factor = left[r][col].clone();
+                for c in 0..left[r].len() {
+                    left[r][c] = left[r][c].clone() - factor.clone() * left[rank][c].clone();
+                }
+                for c in 0..right[r].len() {
+                    right[r][c] = right[r][c].clone() - factor.clone() * right[rank][c].clone();
+                }
+            }
+            rank += 1;
+        }
+        (rank, Matrix { rows: right })
+    }
+
+    pub fn rank(&self) -> usize {
+        self.eliminate(&Matrix::new(vec![vec![]; self.height()])).0
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        if !self.is_square() {
+            return None;
+        }
+        let (rank, inverse) = self.eliminate(&Matrix::identity(self.height()));
+        if rank == self.height() {
+            Some(inverse)
+        } else {
+            None
+        }
+    }
+
+    /// The `x` where `self * x = b`, if there is exactly one
+    pub fn solve(&self, b: &[Rational<T>]) -> Option<Vec<Rational<T>>> {
+        if b.len() != self.height() {
+            panic!("{} values for {} equations", b.len(), self.height());
+        }
+        let column = Matrix::new(b.iter().map(|v| vec![v.clone()]).collect());
+        let (rank, solved) = self.eliminate(&column);
+        if rank != self.width() {
+            return None;
+        }
+        // any left over equations must have reduced to 0 = 0
+        let zero = Rational::<T>::zero();
+        if solved.rows[rank..].iter().any(|r| r[0] != zero) {
+            return None;
+        }
+        Some(solved.rows[..rank].iter().map(|r| r[0].clone()).collect())
+    }
+}
+
+impl<T> From<Vec<Vec<i64>>> for Matrix<Rational<T>>
+where
+    T: SignedNumber + From<i64>,
+{
+    fn from(value: Vec<Vec<i64>>) -> Self {
+        Matrix::new(value).map(|&v| Rational::integer(T::from(v)))
+    }
+}
+
+impl<T> Mul for &Matrix<T>
+where
+    T: Element,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.width() != rhs.height() {
+            panic!(
+                "can't multiply {}x{} by {}x{}",
+                self.height(),
+                self.width(),
+                rhs.height(),
+                rhs.width()
+            );
+        }
+        let columns = rhs.transpose();
+        Matrix {
+            rows: self
+                .rows
+                .iter()
+                .map(|r| columns.rows.iter().map(|c| columns_dot(r, c)).collect())
+                .collect(),
+        }
+    }
+}
+
+impl<T> Mul for Matrix<T>
+where
+    T: Element,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+fn columns_dot<T>(a: &[T], b: &[T]) -> T
+where
+    T: Element,
+{
+    a.iter()
+        .zip(b)
+        .fold(T::zero(), |acc, (x, y)| acc + x.clone() * y.clone())
+}
+
+impl<T> fmt::Display for Matrix<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(f, "[{}]", cells.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// All 24 rotations of 3D space which keep the axes aligned,
+/// such as the orientations a scanner could be facing.
+pub fn rotations_3d() -> Vec<Matrix<i64>> {
+    let mut rotations = Vec::new();
+    let permutations = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    for axes in permutations {
+        for signs in 0..8 {
+            let mut rows = vec![vec![0; 3]; 3];
+            for (row, &axis) in axes.iter().enumerate() {
+                rows[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+            }
+            let m = Matrix::new(rows);
+            // the other half are reflections
+            if m.determinant() == 1 {
+                rotations.push(m);
+            }
+        }
+    }
+    rotations
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn rationals(rows: Vec<Vec<i64>>) -> Matrix<Rational> {
+        rows.into()
+    }
+
+    #[test]
+    fn test_multiply() {
+        let a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::new(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+        assert_eq!(Matrix::new(vec![vec![58, 64], vec![139, 154]]), &a * &b);
+        assert_eq!(a.clone(), &Matrix::identity(2) * &a);
+        assert_eq!(vec![14, 32], a.apply(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(
+            Matrix::new(vec![vec![1, 4], vec![2, 5], vec![3, 6]]),
+            a.transpose()
+        );
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(-2, Matrix::new(vec![vec![1, 2], vec![3, 4]]).determinant());
+        let m = Matrix::new(vec![vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]]);
+        assert_eq!(49, m.determinant());
+        // needs a row swap for a zero pivot
+        let swap = Matrix::new(vec![vec![0, 1], vec![1, 0]]);
+        assert_eq!(-1, swap.determinant());
+        let singular = Matrix::new(vec![vec![1, 2], vec![2, 4]]);
+        assert_eq!(0, singular.determinant());
+        assert_eq!(
+            Rational::new(-1, 2),
+            rationals(vec![vec![1, 2], vec![3, 4]]).determinant() / Rational::from(4)
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = rationals(vec![vec![4, 7], vec![2, 6]]);
+        let inverse = m.inverse().unwrap();
+        assert_eq!(Rational::new(3, 5), *inverse.at(0, 0));
+        assert_eq!(Rational::new(-7, 10), *inverse.at(0, 1));
+        assert_eq!(Matrix::identity(2), &m * &inverse);
+
+        assert_eq!(None, rationals(vec![vec![1, 2], vec![2, 4]]).inverse());
+    }
+
+    #[test]
+    fn test_solve() {
+        //  x + y + z = 6
+        //      2y + 5z = -4
+        // 2x + 5y - z = 27
+        let a = rationals(vec![vec![1, 1, 1], vec![0, 2, 5], vec![2, 5, -1]]);
+        let b: Vec<Rational> = vec![6.into(), (-4).into(), 27.into()];
+        assert_eq!(Some(vec![5.into(), 3.into(), (-2).into()]), a.solve(&b));
+        assert_eq!(3, a.rank());
+
+        let dependent = rationals(vec![vec![1, 1], vec![2, 2]]);
+        assert_eq!(None, dependent.solve(&[1.into(), 2.into()]));
+        assert_eq!(1, dependent.rank());
+
+        // more equations than unknowns, but consistent
+        let tall = rationals(vec![vec![1, 0], vec![0, 1], vec![1, 1]]);
+        assert_eq!(
+            Some(vec![1.into(), 2.into()]),
+            tall.solve(&[1.into(), 2.into(), 3.into()])
+        );
+        assert_eq!(None, tall.solve(&[1.into(), 2.into(), 4.into()]));
+    }
+
+    #[test]
+    fn test_solve_rock_through_hailstones() {
+        // a rock thrown from p with velocity v hits every hailstone, so
+        // (p - p_i) x (v - v_i) = 0. The p x v term is shared by every
+        // hailstone, so subtracting pairs leaves linear equations.
+        let hail: [([i64; 3], [i64; 3]); 3] = [
+            ([19, 13, 30], [-2, 1, -2]),
+            ([18, 19, 22], [-1, -1, -2]),
+            ([20, 25, 34], [-2, -2, -4]),
+        ];
+        let cross = |a: [i64; 3], b: [i64; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let mut rows = vec![];
+        let mut values = vec![];
+        let (p0, v0) = hail[0];
+        for &(pi, vi) in &hail[1..] {
+            let dv = [v0[0] - vi[0], v0[1] - vi[1], v0[2] - vi[2]];
+            let dp = [p0[0] - pi[0], p0[1] - pi[1], p0[2] - pi[2]];
+            let c0 = cross(p0, v0);
+            let ci = cross(pi, vi);
+            // p x (v0 - vi) + (p0 - pi) x v = p0 x v0 - pi x vi, one row per axis
+            for axis in 0..3 {
+                let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+                let mut row = vec![0; 6];
+                row[a] = dv[b];
+                row[b] = -dv[a];
+                row[3 + b] = dp[a];
+                row[3 + a] = -dp[b];
+                rows.push(row);
+                values.push(Rational::from(c0[axis] - ci[axis]));
+            }
+        }
+        let solved = rationals(rows).solve(&values).unwrap();
+        let expected: Vec<Rational> = [24, 13, 10, -3, 1, 2].map(Rational::from).to_vec();
+        assert_eq!(expected, solved);
+    }
+
+    #[test]
+    fn test_rotations_3d() {
+        let rotations = rotations_3d();
+        assert_eq!(24, rotations.len());
+        let unique: HashSet<_> = rotations.iter().collect();
+        assert_eq!(24, unique.len());
+        assert!(rotations.iter().all(|r| r.determinant() == 1));
+        assert!(rotations.contains(&Matrix::identity(3)));
+
+        // rotating 90° about z four times returns to the start
+        let quarter_z = Matrix::new(vec![vec![0, -1, 0], vec![1, 0, 0], vec![0, 0, 1]]);
+        assert!(rotations.contains(&quarter_z));
+        let full = &(&quarter_z * &quarter_z) * &(&quarter_z * &quarter_z);
+        assert_eq!(Matrix::identity(3), full);
+    }
+}
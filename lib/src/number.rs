@@ -1,17 +1,27 @@
 use std::{
     fmt::Display,
-    ops::{Add, Div, Mul, Rem, Sub},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
 
+mod bigint;
+mod rational;
+
+pub use bigint::BigInt;
+pub use rational::Rational;
+
+/// Integer like types, from the primitives through to [`BigInt`].
+///
+/// Only `Clone` is required rather than `Copy` so that arbitrary precision
+/// types qualify, switching between them shouldn't change any logic.
 pub trait Number:
     From<u8>
     + PartialEq
-    + Add
-    + Sub
+    + Add<Output = Self>
+    + Sub<Output = Self>
     + Mul<Output = Self>
     + Div<Output = Self>
     + Rem<Output = Self>
-    + Copy
+    + Clone
     + Display
 {
 }
@@ -19,41 +29,204 @@ pub trait Number:
 impl<T> Number for T where
     T: From<u8>
         + PartialEq
-        + Add
-        + Sub
+        + Add<Output = Self>
+        + Sub<Output = Self>
         + Mul<Output = Self>
         + Div<Output = Self>
         + Rem<Output = Self>
-        + Copy
+        + Clone
         + Display
 {
 }
 
+/// A [`Number`] which can also be negative and ordered, as needed by [`Rational`]
+pub trait SignedNumber: Number + Ord + Neg<Output = Self> {
+    fn abs(self) -> Self {
+        if self < 0.into() {
+            -self
+        } else {
+            self
+        }
+    }
+}
+
+impl<T> SignedNumber for T where T: Number + Ord + Neg<Output = Self> {}
+
+/// Lowest common multiple of all the numbers, `1` when there are none.
+///
+/// Divides before multiplying so it only overflows if the result itself does.
+/// Any zero makes the result zero.
 pub fn lcm<T>(nums: &[T]) -> T
 where
     T: Number,
 {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    let prod = a * b;
-    prod / gcd_of_two_numbers(a, b)
+    nums.iter().fold(1.into(), |acc, n| {
+        if acc == 0.into() || *n == 0.into() {
+            0.into()
+        } else {
+            acc.clone() / gcd(acc, n.clone()) * n.clone()
+        }
+    })
 }
 
-fn gcd_of_two_numbers<T>(a: T, b: T) -> T
+/// Greatest common divisor using the Euclidean algorithm.
+///
+/// For signed numbers pass absolute values to get a positive result.
+pub fn gcd<T>(a: T, b: T) -> T
 where
     T: Number,
 {
-    if b == 0.into() {
-        return a;
+    let (mut a, mut b) = (a, b);
+    while b != 0.into() {
+        (a, b) = (b.clone(), a % b);
+    }
+    a
+}
+
+/// The greatest common divisor `g` along with `x` and `y` such that `a*x + b*y = g`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
     }
-    gcd_of_two_numbers(b, a % b)
+}
+
+/// `a` reduced into `0..m`, even when negative
+pub fn modulo(a: i128, m: i128) -> i128 {
+    a.rem_euclid(m)
+}
+
+/// The `x` in `0..m` where `a*x ≡ 1 (mod m)`, if `a` and `m` are coprime
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    if g == 1 {
+        Some(modulo(x, m))
+    } else {
+        None
+    }
+}
+
+/// `base^exp (mod m)` by repeated squaring.
+///
+/// Intermediate products are held in an `i128` so `m` must fit in an `i64`.
+pub fn mod_pow(base: i128, exp: u64, m: i128) -> i128 {
+    let mut result = modulo(1, m);
+    let mut base = modulo(base, m);
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for each `(residue, modulus)` pair
+/// using the Chinese Remainder Theorem.
+///
+/// The moduli need not be coprime. Returns the smallest non-negative `x`
+/// with the lcm of the moduli, all solutions being `x + k*lcm`,
+/// or `None` when the congruences contradict each other.
+///
+/// ```
+/// use aoclib::number::crt;
+///
+/// assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+/// assert_eq!(None, crt(&[(1, 4), (2, 6)]));
+/// ```
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(residue, modulus)| {
+            let (g, p, _) = extended_gcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+            let step = modulus / g;
+            let t = modulo(modulo(diff / g, step) * modulo(p, step), step);
+            let lcm = m * step;
+            Some((modulo(x + m * t, lcm), lcm))
+        })
 }
 
 #[test]
 fn test_lcm_examples() {
     let n = [1, 2, 3];
     assert_eq!(6, lcm(&n));
+    assert_eq!(12, lcm(&[4, 6]));
+    assert_eq!(7, lcm(&[7]));
+}
+
+#[test]
+fn test_lcm_does_not_overflow_early() {
+    // the product of these alone would overflow a u64
+    let n: [u64; 2] = [4_000_000_000_000_000_000, 2_000_000_000_000_000_000];
+    assert_eq!(4_000_000_000_000_000_000, lcm(&n));
+
+    let cycles: [i128; 4] = [3739, 3919, 4027, 4003];
+    assert_eq!(3739 * 3919 * 4027 * 4003, lcm(&cycles));
+}
+
+#[test]
+fn test_lcm_with_zero() {
+    assert_eq!(0, lcm(&[0_u64, 0]));
+    assert_eq!(0, lcm(&[4_u64, 0, 6]));
+    assert_eq!(0, lcm(&[0_i128, 5]));
+}
+
+#[test]
+fn test_gcd() {
+    assert_eq!(6, gcd(48_u64, 18));
+    assert_eq!(5, gcd(0_i128, 5));
+    assert_eq!(1, gcd(17_usize, 4));
+}
+
+#[test]
+fn test_extended_gcd() {
+    let (g, x, y) = extended_gcd(240, 46);
+    assert_eq!(2, g);
+    assert_eq!(2, 240 * x + 46 * y);
+
+    let (g, x, y) = extended_gcd(-12, 18);
+    assert_eq!(6, g);
+    assert_eq!(6, -12 * x + 18 * y);
+}
+
+#[test]
+fn test_mod_inverse() {
+    assert_eq!(Some(4), mod_inverse(3, 11));
+    assert_eq!(Some(7), mod_inverse(-3, 11));
+    assert_eq!(None, mod_inverse(6, 9));
+}
+
+#[test]
+fn test_mod_pow() {
+    assert_eq!(445, mod_pow(4, 13, 497));
+    assert_eq!(1, mod_pow(7, 0, 13));
+    assert_eq!(0, mod_pow(7, 5, 1));
+    assert_eq!(9, mod_pow(-2, 3, 17));
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+    // non coprime moduli
+    assert_eq!(Some((10, 12)), crt(&[(2, 4), (4, 6)]));
+    assert_eq!(None, crt(&[(1, 4), (2, 6)]));
+    // negative residues are normalised
+    assert_eq!(Some((4, 5)), crt(&[(-1, 5)]));
+    assert_eq!(Some((0, 1)), crt(&[]));
 }
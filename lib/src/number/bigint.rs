@@ -0,0 +1,454 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+    str::FromStr,
+};
+
+/// An arbitrary precision signed integer.
+///
+/// Implements [`Number`](super::Number) so can stand in for a primitive
+/// integer wherever calculations would otherwise overflow.
+/// Division truncates towards zero, matching the primitives.
+///
+/// ```
+/// use aoclib::number::BigInt;
+///
+/// let a: BigInt = "123456789012345678901234567890".parse().unwrap();
+/// let b = BigInt::from(1_000_000_007);
+/// assert_eq!("123456789876543201987654320198641975230", format!("{}", a * b));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    /// little endian base 2^32 digits, with no trailing zeros
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt::from_parts(false, self.magnitude.clone())
+    }
+
+    pub fn pow(&self, exp: u32) -> Self {
+        let mut result = BigInt::from(1);
+        let mut base = self.clone();
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base.clone();
+            }
+            base = base.clone() * base;
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0_u64;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// `a - b` where `a` is at least as large as `b`
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0_i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut diff = digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = if diff < 0 {
+            diff += 1 << 32;
+            1
+        } else {
+            0
+        };
+        result.push(diff as u32);
+    }
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0_u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, &y) in b.iter().enumerate() {
+            let total = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
+
+/// Quotient and remainder of the magnitudes, `b` must not be zero
+fn divrem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if let [divisor] = b {
+        let divisor = *divisor as u64;
+        let mut quotient = vec![0_u32; a.len()];
+        let mut remainder = 0_u64;
+        for i in (0..a.len()).rev() {
+            let current = (remainder << 32) | a[i] as u64;
+            quotient[i] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        return (quotient, vec![remainder as u32]);
+    }
+
+    // binary long division, one bit at a time
+    let mut quotient = vec![0_u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for i in (0..a.len() * 32).rev() {
+        let bit = (a[i / 32] >> (i % 32)) & 1;
+        let mut carry = bit;
+        for digit in remainder.iter_mut() {
+            let shifted = (*digit as u64) << 1 | carry as u64;
+            *digit = shifted as u32;
+            carry = (shifted >> 32) as u32;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+        if cmp_magnitude(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, b);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[i / 32] |= 1 << (i % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
+impl BigInt {
+    fn divrem(self, rhs: BigInt) -> (BigInt, BigInt) {
+        if rhs.is_zero() {
+            panic!("attempt to divide by zero");
+        }
+        let (quotient, remainder) = divrem_magnitude(&self.magnitude, &rhs.magnitude);
+        (
+            BigInt::from_parts(self.negative != rhs.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        )
+    }
+}
+
+impl From<u8> for BigInt {
+    fn from(value: u8) -> Self {
+        BigInt::from(value as u128)
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(value: i32) -> Self {
+        BigInt::from(value as i128)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        BigInt::from(value as i128)
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        BigInt::from(value as u128)
+    }
+}
+
+impl From<usize> for BigInt {
+    fn from(value: usize) -> Self {
+        BigInt::from(value as u128)
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        let magnitude = (0..4).map(|i| (value >> (32 * i)) as u32).collect();
+        BigInt::from_parts(false, magnitude)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let BigInt { magnitude, .. } = BigInt::from(value.unsigned_abs());
+        BigInt::from_parts(value < 0, magnitude)
+    }
+}
+
+impl TryFrom<BigInt> for i128 {
+    type Error = String;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        if value.magnitude.len() > 4 {
+            return Err(format!("{value} does not fit in an i128"));
+        }
+        let unsigned = value
+            .magnitude
+            .iter()
+            .enumerate()
+            .fold(0_u128, |acc, (i, &d)| acc | (d as u128) << (32 * i));
+        if value.negative {
+            0_i128
+                .checked_sub_unsigned(unsigned)
+                .ok_or_else(|| format!("{value} does not fit in an i128"))
+        } else {
+            i128::try_from(unsigned).map_err(|_| format!("{value} does not fit in an i128"))
+        }
+    }
+}
+
+impl TryFrom<BigInt> for i64 {
+    type Error = String;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        let wide = i128::try_from(value)?;
+        i64::try_from(wide).map_err(|_| format!("{wide} does not fit in an i64"))
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("bad integer {s:?}"));
+        }
+        let ten = BigInt::from(10);
+        let value = digits.chars().fold(BigInt::zero(), |acc, c| {
+            acc * ten.clone() + BigInt::from(c as u8 - b'0')
+        });
+        Ok(if negative { -value } else { value })
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+        // peel off 9 decimal digits at a time
+        let mut chunks = Vec::new();
+        let mut remaining = self.magnitude.clone();
+        while !remaining.is_empty() {
+            let (quotient, remainder) = divrem_magnitude(&remaining, &[1_000_000_000]);
+            chunks.push(remainder[0]);
+            remaining = BigInt::from_parts(false, quotient).magnitude;
+        }
+        if self.negative {
+            f.write_str("-")?;
+        }
+        let mut chunks = chunks.into_iter().rev();
+        write!(f, "{}", chunks.next().unwrap_or(0))?;
+        for chunk in chunks {
+            write!(f, "{chunk:09}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl Add for BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(
+                self.negative,
+                add_magnitude(&self.magnitude, &rhs.magnitude),
+            );
+        }
+        match cmp_magnitude(&self.magnitude, &rhs.magnitude) {
+            Ordering::Less => {
+                BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.magnitude, &self.magnitude))
+            }
+            _ => BigInt::from_parts(
+                self.negative,
+                sub_magnitude(&self.magnitude, &rhs.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_magnitude(&self.magnitude, &rhs.magnitude),
+        )
+    }
+}
+
+impl Div for BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.divrem(rhs).0
+    }
+}
+
+impl Rem for BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.divrem(rhs).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::{gcd, lcm};
+
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for s in [
+            "0",
+            "7",
+            "-7",
+            "4294967296",
+            "-1000000000000000000000000000001",
+        ] {
+            assert_eq!(s, format!("{}", big(s)));
+        }
+        assert_eq!(BigInt::zero(), big("-0"));
+        assert!(" 1".parse::<BigInt>().is_err());
+        assert!("".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn test_matches_primitive_arithmetic() {
+        let values: [i128; 8] = [0, 1, -1, 7, -13, 4294967295, -4294967296, 123456789012345];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(BigInt::from(a + b), x.clone() + y.clone(), "{a} + {b}");
+                assert_eq!(BigInt::from(a - b), x.clone() - y.clone(), "{a} - {b}");
+                assert_eq!(BigInt::from(a * b), x.clone() * y.clone(), "{a} * {b}");
+                assert_eq!(a.cmp(&b), x.cmp(&y), "{a} cmp {b}");
+                if b != 0 {
+                    assert_eq!(BigInt::from(a / b), x.clone() / y.clone(), "{a} / {b}");
+                    assert_eq!(BigInt::from(a % b), x % y, "{a} % {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_division() {
+        let a = big("340282366920938463463374607431768211457");
+        let b = big("18446744073709551617");
+        assert_eq!(big("18446744073709551615"), a.clone() / b.clone());
+        assert_eq!(big("2"), a % b);
+    }
+
+    #[test]
+    fn test_pow_and_conversion() {
+        let two_100 = BigInt::from(2).pow(100);
+        assert_eq!("1267650600228229401496703205376", format!("{two_100}"));
+        assert!(i128::try_from(two_100.clone()).is_ok());
+        assert!(i128::try_from(two_100.pow(2)).is_err());
+        assert_eq!(Ok(i128::MIN), i128::try_from(BigInt::from(i128::MIN)));
+        assert_eq!(Ok(-5_i64), i64::try_from(BigInt::from(-5)));
+    }
+
+    #[test]
+    fn test_works_as_number() {
+        assert_eq!(BigInt::from(6), gcd(BigInt::from(48), BigInt::from(18)));
+
+        // would overflow a u128
+        let primes = [
+            BigInt::from(1_000_000_007),
+            BigInt::from(998_244_353),
+            BigInt::from(1_000_000_009),
+            BigInt::from(999_999_937),
+            BigInt::from(2_147_483_647),
+        ];
+        let expected = primes
+            .iter()
+            .cloned()
+            .fold(BigInt::from(1), |acc, p| acc * p);
+        assert_eq!(expected, lcm(&primes));
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::{gcd, BigInt, SignedNumber};
+
+/// An exact fraction, always held in lowest terms with a positive denominator
+/// so that equal values compare and hash equally.
+///
+/// Defaults to `i128` parts, use `Rational<BigInt>` when even those overflow.
+///
+/// ```
+/// use aoclib::number::Rational;
+///
+/// let half: Rational = Rational::new(2, 4);
+/// assert_eq!(Rational::new(1, 2), half);
+/// assert_eq!(Rational::from(1), half + half);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T = i128> {
+    num: T,
+    den: T,
+}
+
+impl<T> Rational<T>
+where
+    T: SignedNumber,
+{
+    pub fn new(num: T, den: T) -> Self {
+        let zero: T = 0.into();
+        if den == zero {
+            panic!("denominator must not be zero for {num}/{den}");
+        }
+        let divisor = gcd(num.clone().abs(), den.clone().abs());
+        let (num, den) = (num / divisor.clone(), den / divisor);
+        if den < zero {
+            Rational {
+                num: -num,
+                den: -den,
+            }
+        } else {
+            Rational { num, den }
+        }
+    }
+
+    /// The whole number `value`
+    pub fn integer(value: T) -> Self {
+        Rational {
+            num: value,
+            den: 1.into(),
+        }
+    }
+
+    pub fn numerator(&self) -> T {
+        self.num.clone()
+    }
+
+    pub fn denominator(&self) -> T {
+        self.den.clone()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1.into()
+    }
+
+    /// The value as an integer, if it is one
+    pub fn to_integer(&self) -> Option<T> {
+        if self.is_integer() {
+            Some(self.num.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(value: i32) -> Self {
+        Rational::integer(value as i128)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational::integer(value as i128)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational::integer(value)
+    }
+}
+
+impl From<BigInt> for Rational<BigInt> {
+    fn from(value: BigInt) -> Self {
+        Rational::integer(value)
+    }
+}
+
+impl<T> Add for Rational<T>
+where
+    T: SignedNumber,
+{
+    type Output = Rational<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Rational::new(
+            self.num * rhs.den.clone() + rhs.num * self.den.clone(),
+            self.den * rhs.den,
+        )
+    }
+}
+
+impl<T> Sub for Rational<T>
+where
+    T: SignedNumber,
+{
+    type Output = Rational<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Rational::new(
+            self.num * rhs.den.clone() - rhs.num * self.den.clone(),
+            self.den * rhs.den,
+        )
+    }
+}
+
+impl<T> Mul for Rational<T>
+where
+    T: SignedNumber,
+{
+    type Output = Rational<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl<T> Div for Rational<T>
+where
+    T: SignedNumber,
+{
+    type Output = Rational<T>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl<T> Neg for Rational<T>
+where
+    T: SignedNumber,
+{
+    type Output = Rational<T>;
+
+    fn neg(self) -> Self::Output {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl<T> Ord for Rational<T>
+where
+    T: SignedNumber,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are always positive so cross multiplying keeps the order
+        (self.num.clone() * other.den.clone()).cmp(&(other.num.clone() * self.den.clone()))
+    }
+}
+
+impl<T> PartialOrd for Rational<T>
+where
+    T: SignedNumber,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Display for Rational<T>
+where
+    T: SignedNumber,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rational_normalised() {
+        assert_eq!(Rational::new(1, 2), Rational::new(-3, -6));
+        assert_eq!(Rational::new(-1, 2), Rational::new(2, -4));
+        assert_eq!(-1, Rational::new(2, -4).numerator());
+        assert_eq!(2, Rational::new(2, -4).denominator());
+        assert_eq!(Some(3), Rational::new(9, 3).to_integer());
+        assert_eq!(None, Rational::new(9, 4).to_integer());
+    }
+
+    #[test]
+    fn test_rational_arithmetic() {
+        let third = Rational::new(1, 3);
+        let half = Rational::new(1, 2);
+        assert_eq!(Rational::new(5, 6), third + half);
+        assert_eq!(Rational::new(-1, 6), third - half);
+        assert_eq!(Rational::new(1, 6), third * half);
+        assert_eq!(Rational::new(2, 3), third / half);
+        assert!(third < half);
+        assert!(-half < -third);
+        assert_eq!("5/6", format!("{}", third + half));
+        assert_eq!("1", format!("{}", half + half));
+    }
+
+    #[test]
+    fn test_rational_other_integer_types() {
+        let small: Rational<i64> = Rational::new(6, -8);
+        assert_eq!(Rational::new(-3_i64, 4), small);
+
+        let big = Rational::new(BigInt::from(1_u64 << 62), BigInt::from(3));
+        let sum = big.clone() + big.clone() + big;
+        assert_eq!(Some(BigInt::from(1_u64 << 62)), sum.to_integer());
+    }
+
+    #[test]
+    fn test_rational_big_beyond_i128() {
+        let huge: BigInt = "1000000000000000000000000000000000000000000"
+            .parse()
+            .unwrap();
+        let a = Rational::new(huge.clone(), BigInt::from(7));
+        let b = Rational::new(BigInt::from(1), huge.clone());
+        let product = a * b;
+        assert_eq!(Rational::new(BigInt::from(1), BigInt::from(7)), product);
+        assert_eq!("1/7", format!("{product}"));
+    }
+}
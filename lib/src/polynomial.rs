@@ -0,0 +1,182 @@
+use crate::number::Rational;
+
+/// The table of repeated differences for a sequence of equally spaced values.
+///
+/// ```ignore
+/// 1   3   6  10  15  21
+///   2   3   4   5   6
+///     1   1   1   1
+///       0   0   0
+/// ```
+///
+/// Any sequence generated by a polynomial eventually reaches a row of
+/// zeros, which allows values beyond either end to be extrapolated.
+///
+/// ```
+/// use aoclib::polynomial::DifferenceTable;
+///
+/// let table = DifferenceTable::new(vec![1, 3, 6, 10, 15, 21]);
+/// assert_eq!(Some(2), table.degree());
+/// assert_eq!(28, table.next_value());
+/// assert_eq!(0, table.previous_value());
+/// assert_eq!(5050, table.value_at(99));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferenceTable(Vec<Vec<i64>>);
+
+impl DifferenceTable {
+    pub fn new(values: Vec<i64>) -> Self {
+        let mut rows = vec![values];
+        loop {
+            let last = rows.last().expect("always a row");
+            if last.len() <= 1 || last.iter().all(|&i| i == 0) {
+                break;
+            }
+            let next = last.windows(2).map(|w| w[1] - w[0]).collect();
+            rows.push(next);
+        }
+        DifferenceTable(rows)
+    }
+
+    /// The original values followed by each row of differences
+    pub fn rows(&self) -> &[Vec<i64>] {
+        &self.0
+    }
+
+    /// The degree of the polynomial generating the values,
+    /// if there were enough values to reach a row of zeros.
+    pub fn degree(&self) -> Option<usize> {
+        let last = self.0.last()?;
+        if !last.is_empty() && last.iter().all(|&i| i == 0) {
+            Some(self.0.len().saturating_sub(2))
+        } else {
+            None
+        }
+    }
+
+    /// The value following the last one
+    pub fn next_value(&self) -> i64 {
+        self.0.iter().filter_map(|row| row.last()).sum()
+    }
+
+    /// The value preceding the first one
+    pub fn previous_value(&self) -> i64 {
+        self.0
+            .iter()
+            .filter_map(|row| row.first())
+            .rev()
+            .fold(0, |below, &first| first - below)
+    }
+
+    /// The value at index `x`, where the first value is at `0`,
+    /// using Newton's forward difference formula.
+    ///
+    /// `x` can be far beyond the sampled values, or negative.
+    pub fn value_at(&self, x: i64) -> i128 {
+        let x = x as i128;
+        let mut binomial: i128 = 1;
+        let mut result: i128 = 0;
+        for (k, row) in self.0.iter().enumerate() {
+            let Some(&first) = row.first() else {
+                break;
+            };
+            if k > 0 {
+                let k = k as i128;
+                // C(x, k) from C(x, k - 1), always divides exactly
+                binomial = binomial * (x - k + 1) / k;
+            }
+            result += binomial * first as i128;
+        }
+        result
+    }
+}
+
+/// The value at `x` of the lowest degree polynomial passing through all `points`,
+/// using Lagrange interpolation. The `x` values of the points must all differ
+/// but need not be equally spaced.
+///
+/// ```
+/// use aoclib::{number::Rational, polynomial::lagrange};
+///
+/// let points = [(0, 1), (2, 5), (3, 10)];
+/// assert_eq!(Rational::from(26), lagrange(&points, 5));
+/// ```
+pub fn lagrange(points: &[(i64, i64)], x: i64) -> Rational {
+    let x = Rational::from(x);
+    points
+        .iter()
+        .enumerate()
+        .fold(Rational::from(0), |total, (i, &(xi, yi))| {
+            let basis = points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Rational::from(1), |basis, (_, &(xj, _))| {
+                    basis * (x - Rational::from(xj)) / Rational::from(xi - xj)
+                });
+            total + basis * Rational::from(yi)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difference_table_rows() {
+        let table = DifferenceTable::new(vec![10, 13, 16, 21, 30, 45]);
+        assert_eq!(
+            &[
+                vec![10, 13, 16, 21, 30, 45],
+                vec![3, 3, 5, 9, 15],
+                vec![0, 2, 4, 6],
+                vec![2, 2, 2],
+                vec![0, 0],
+            ],
+            table.rows()
+        );
+        assert_eq!(Some(3), table.degree());
+    }
+
+    #[test]
+    fn test_extrapolate_both_ways() {
+        let table = DifferenceTable::new(vec![10, 13, 16, 21, 30, 45]);
+        assert_eq!(68, table.next_value());
+        assert_eq!(5, table.previous_value());
+        assert_eq!(68, table.value_at(6));
+        assert_eq!(5, table.value_at(-1));
+    }
+
+    #[test]
+    fn test_constant_and_short_sequences() {
+        let constant = DifferenceTable::new(vec![3, 3, 3]);
+        assert_eq!(Some(0), constant.degree());
+        assert_eq!(3, constant.next_value());
+        assert_eq!(3, constant.value_at(1000));
+
+        let single = DifferenceTable::new(vec![7]);
+        assert_eq!(None, single.degree());
+        assert_eq!(7, single.next_value());
+        assert_eq!(7, single.previous_value());
+    }
+
+    #[test]
+    fn test_value_at_quadratic_growth() {
+        // f(x) = 3x^2 - 2x + 7
+        let f = |x: i128| 3 * x * x - 2 * x + 7;
+        let table = DifferenceTable::new((0..3).map(|x| f(x) as i64).collect());
+        assert_eq!(f(202300), table.value_at(202300));
+        assert_eq!(f(-50), table.value_at(-50));
+    }
+
+    #[test]
+    fn test_lagrange() {
+        // f(x) = x^2 / 2, not integer at odd x
+        let points = [(0, 0), (2, 2), (4, 8)];
+        assert_eq!(Rational::new(9, 2), lagrange(&points, 3));
+        assert_eq!(Rational::from(50), lagrange(&points, 10));
+
+        let unordered = [(4, 8), (0, 0), (2, 2)];
+        assert_eq!(Rational::new(1, 2), lagrange(&unordered, -1));
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+
+use crate::cartesian::{Point, Transform};
+
+/// Where a knot moves to once the knot ahead of it has moved
+pub type FollowRule = fn(leader: &Point, follower: &Point) -> Point;
+
+/// Stays put while still touching the leader, including diagonally,
+/// otherwise steps one towards it along each axis it is behind on
+pub fn chebyshev(leader: &Point, follower: &Point) -> Point {
+    let (dx, dy) = (leader.x - follower.x, leader.y - follower.y);
+    if dx.abs() <= 1 && dy.abs() <= 1 {
+        follower.clone()
+    } else {
+        Point::new(follower.x + dx.signum(), follower.y + dy.signum())
+    }
+}
+
+/// `N` knots, each following the one before it, remembering everywhere each knot has been.
+///
+/// ```
+/// use aoclib::{cartesian::{Direction, Point}, rope::Rope};
+///
+/// let mut rope: Rope<3> = Rope::new(Point::new(0, 0));
+/// rope.step(Direction::Right, 4);
+/// assert_eq!(&Point::new(2, 0), rope.tail());
+/// assert_eq!(3, rope.visited(2).len());
+/// assert_eq!("21H", rope.render());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rope<const N: usize> {
+    knots: [Point; N],
+    visited: [HashSet<Point>; N],
+    follow: FollowRule,
+}
+
+impl<const N: usize> Rope<N> {
+    /// Every knot starting at `start`, following with [`chebyshev`]
+    pub fn new(start: Point) -> Self {
+        Self::from_knots(std::array::from_fn(|_| start.clone()))
+    }
+
+    /// A rope already laid out, from the head to the tail
+    pub fn from_knots(knots: [Point; N]) -> Self {
+        assert!(N > 0, "a rope needs at least one knot");
+        Rope {
+            visited: std::array::from_fn(|i| HashSet::from([knots[i].clone()])),
+            knots,
+            follow: chebyshev,
+        }
+    }
+
+    pub fn with_rule(self, follow: FollowRule) -> Self {
+        Rope { follow, ..self }
+    }
+
+    pub fn knots(&self) -> &[Point; N] {
+        &self.knots
+    }
+
+    pub fn head(&self) -> &Point {
+        &self.knots[0]
+    }
+
+    pub fn tail(&self) -> &Point {
+        &self.knots[N - 1]
+    }
+
+    /// Everywhere the `knot`th knot has been, the head being knot 0
+    pub fn visited(&self, knot: usize) -> &HashSet<Point> {
+        &self.visited[knot]
+    }
+
+    /// Moves the head by `t` then lets the rest of the rope follow
+    pub fn move_head(&mut self, t: &Transform) {
+        self.knots[0] = self.knots[0].transform(t);
+        self.visited[0].insert(self.knots[0].clone());
+        for i in 1..N {
+            let moved = (self.follow)(&self.knots[i - 1], &self.knots[i]);
+            self.visited[i].insert(moved.clone());
+            self.knots[i] = moved;
+        }
+    }
+
+    /// Moves the head one step at a time, `steps` times
+    pub fn step(&mut self, direction: impl Into<Transform>, steps: usize) {
+        let t: Transform = direction.into();
+        for _ in 0..steps {
+            self.move_head(&t);
+        }
+    }
+
+    /// The rope drawn within the smallest box around it, with `y` increasing upwards.
+    ///
+    /// The head is `H` and each other knot its number, where knots overlap
+    /// the one nearer the head is drawn.
+    pub fn render(&self) -> String {
+        let min_x = self.knots.iter().map(|k| k.x).min().unwrap();
+        let max_x = self.knots.iter().map(|k| k.x).max().unwrap();
+        let min_y = self.knots.iter().map(|k| k.y).min().unwrap();
+        let max_y = self.knots.iter().map(|k| k.y).max().unwrap();
+
+        let rows: Vec<String> = (min_y..=max_y)
+            .rev()
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| {
+                        let p = Point::new(x, y);
+                        match self.knots.iter().position(|k| *k == p) {
+                            Some(0) => 'H',
+                            Some(i) => char::from_digit((i % 36) as u32, 36).unwrap(),
+                            None => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        rows.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cartesian::Direction;
+
+    use super::*;
+
+    #[test]
+    fn test_chebyshev() {
+        let origin = Point::new(0, 0);
+        for touching in [(0, 0), (1, 0), (1, 1), (-1, 1), (0, -1)] {
+            assert_eq!(origin, chebyshev(&touching.into(), &origin));
+        }
+        assert_eq!(Point::new(1, 0), chebyshev(&(2, 0).into(), &origin));
+        assert_eq!(Point::new(1, 1), chebyshev(&(2, 1).into(), &origin));
+        assert_eq!(Point::new(-1, -1), chebyshev(&(-2, -2).into(), &origin));
+    }
+
+    #[test]
+    fn test_tail_follows() {
+        let mut rope: Rope<2> = Rope::new((0, 0).into());
+        rope.step(Direction::Right, 4);
+        rope.step(Direction::Up, 4);
+        assert_eq!(&Point::new(4, 4), rope.head());
+        assert_eq!(&Point::new(4, 3), rope.tail());
+        assert_eq!(7, rope.visited(1).len());
+        assert_eq!(9, rope.visited(0).len());
+    }
+
+    #[test]
+    fn test_long_rope_example() {
+        let moves = [
+            (Direction::Right, 5),
+            (Direction::Up, 8),
+            (Direction::Left, 8),
+            (Direction::Down, 3),
+            (Direction::Right, 17),
+            (Direction::Down, 10),
+            (Direction::Left, 25),
+            (Direction::Up, 20),
+        ];
+        let mut rope: Rope<10> = Rope::new((0, 0).into());
+        for (d, n) in moves {
+            rope.step(d, n);
+        }
+        assert_eq!(36, rope.visited(9).len());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut rope: Rope<10> = Rope::new((0, 0).into());
+        rope.step(Direction::Right, 4);
+        rope.step(Direction::Up, 4);
+        let expected = "\
+....H
+....1
+..432
+.5...
+6....";
+        assert_eq!(expected, rope.render());
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        // only touching side by side counts, so a knot diagonally behind catches up
+        fn orthogonal(leader: &Point, follower: &Point) -> Point {
+            if (leader.x - follower.x).abs() + (leader.y - follower.y).abs() > 1 {
+                Point::new(
+                    follower.x + (leader.x - follower.x).signum(),
+                    follower.y + (leader.y - follower.y).signum(),
+                )
+            } else {
+                follower.clone()
+            }
+        }
+        let knots = [Point::new(1, 0), Point::new(0, 0)];
+
+        let mut rope = Rope::from_knots(knots.clone());
+        rope.step(Direction::Up, 1);
+        assert_eq!(&Point::new(0, 0), rope.tail());
+
+        let mut rope = Rope::from_knots(knots).with_rule(orthogonal);
+        rope.step(Direction::Up, 1);
+        assert_eq!(&Point::new(1, 1), rope.tail());
+        assert_eq!(2, rope.visited(1).len());
+    }
+}
@@ -0,0 +1,190 @@
+use std::hash::Hash;
+
+use crate::{cartesian::Direction, cycle, grid::Grid};
+
+/// How a cell behaves when its grid is tilted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Roll {
+    /// Rolls as far as it can
+    Rolls,
+    /// Never moves and stops anything rolling into it
+    Fixed,
+    /// Space something can roll into
+    Empty,
+}
+
+/// Rolls every `Rolls` cell as far as it can towards `direction`,
+/// where `Up` is towards row 0.
+///
+/// Each row or column is compacted in a single pass from the side being tilted towards,
+/// swapping each rolling cell with the space it lands in.
+///
+/// ```
+/// use aoclib::{cartesian::Direction, grid::Grid, tilt::{tilt, Roll}};
+///
+/// let roll = |c: &char| match c {
+///     'O' => Roll::Rolls,
+///     '#' => Roll::Fixed,
+///     _ => Roll::Empty,
+/// };
+/// let mut g: Grid<char> = ".O#.O\nO.O.O".parse().unwrap();
+/// tilt(&mut g, Direction::Left, roll);
+/// assert_eq!("O.#O.".chars().collect::<Vec<_>>(), g.rows[0]);
+/// assert_eq!("OOO..".chars().collect::<Vec<_>>(), g.rows[1]);
+/// ```
+pub fn tilt<T, F>(grid: &mut Grid<T>, direction: Direction, roll: F)
+where
+    F: Fn(&T) -> Roll,
+{
+    let (width, height) = (grid.width(), grid.height());
+    // the (row, col) of the i-th cell along a line, counting from the side tilted towards
+    let at = |line: usize, i: usize| match direction {
+        Direction::Up => (i, line),
+        Direction::Down => (height - 1 - i, line),
+        Direction::Left => (line, i),
+        Direction::Right => (line, width - 1 - i),
+    };
+    let (lines, length) = match direction {
+        Direction::Up | Direction::Down => (width, height),
+        Direction::Left | Direction::Right => (height, width),
+    };
+
+    for line in 0..lines {
+        // where the next rolling cell along this line comes to rest
+        let mut landing = 0;
+        for i in 0..length {
+            let (row, col) = at(line, i);
+            match roll(&grid.rows[row][col]) {
+                Roll::Fixed => landing = i + 1,
+                Roll::Rolls => {
+                    if landing != i {
+                        swap(grid, (row, col), at(line, landing));
+                    }
+                    landing += 1;
+                }
+                Roll::Empty => {}
+            }
+        }
+    }
+}
+
+fn swap<T>(grid: &mut Grid<T>, a: (usize, usize), b: (usize, usize)) {
+    if a.0 == b.0 {
+        grid.rows[a.0].swap(a.1, b.1);
+    } else {
+        let (low, high) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        let (top, bottom) = grid.rows.split_at_mut(high.0);
+        std::mem::swap(&mut top[low.0][low.1], &mut bottom[0][high.1]);
+    }
+}
+
+/// Tilts towards each of `directions` in turn
+pub fn spin<T, F>(grid: &mut Grid<T>, directions: &[Direction], roll: F)
+where
+    F: Fn(&T) -> Roll,
+{
+    for d in directions {
+        tilt(grid, *d, &roll);
+    }
+}
+
+/// The grid after `n` spins through `directions`.
+///
+/// Spinning soon settles into a loop, so only spins until a grid repeats
+/// and works out where in that loop the `n`th spin lands.
+pub fn after_spins<T, F>(grid: Grid<T>, directions: &[Direction], n: usize, roll: F) -> Grid<T>
+where
+    T: Clone + Hash + Eq,
+    F: Fn(&T) -> Roll,
+{
+    let history = cycle::history(
+        grid,
+        |g| {
+            let mut next = g.clone();
+            spin(&mut next, directions, &roll);
+            next
+        },
+        |g| g.rows.clone(),
+    );
+    history.after(n).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roll(c: &char) -> Roll {
+        match c {
+            'O' => Roll::Rolls,
+            '#' => Roll::Fixed,
+            _ => Roll::Empty,
+        }
+    }
+
+    fn grid(txt: &str) -> Grid<char> {
+        txt.parse().unwrap()
+    }
+
+    const SPIN: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Down,
+        Direction::Right,
+    ];
+
+    #[test]
+    fn test_tilt_each_way() {
+        let start = grid("O.#\n.O.\nO.O");
+
+        let mut g = start.clone();
+        tilt(&mut g, Direction::Up, roll);
+        assert_eq!(grid("OO#\nO.O\n..."), g);
+
+        let mut g = start.clone();
+        tilt(&mut g, Direction::Down, roll);
+        assert_eq!(grid("..#\nO..\nOOO"), g);
+
+        let mut g = start.clone();
+        tilt(&mut g, Direction::Left, roll);
+        assert_eq!(grid("O.#\nO..\nOO."), g);
+
+        let mut g = start;
+        tilt(&mut g, Direction::Right, roll);
+        assert_eq!(grid(".O#\n..O\n.OO"), g);
+    }
+
+    #[test]
+    fn test_fixed_cells_stop_rolling() {
+        let mut g = grid("..O#..O.O#O");
+        tilt(&mut g, Direction::Left, roll);
+        assert_eq!(grid("O..#OO...#O"), g);
+        tilt(&mut g, Direction::Right, roll);
+        assert_eq!(grid("..O#...OO#O"), g);
+    }
+
+    #[test]
+    fn test_spin() {
+        let start = grid("O..\n.#.\n..O");
+        let mut once = start.clone();
+        spin(&mut once, &SPIN, roll);
+        assert_eq!(grid("..O\n.#.\n..O"), once);
+
+        assert_eq!(start, after_spins(start.clone(), &SPIN, 0, roll));
+        assert_eq!(once, after_spins(start.clone(), &SPIN, 1, roll));
+
+        let n = 1_000_000_000;
+        let mut spun = after_spins(start.clone(), &SPIN, n, roll);
+        spin(&mut spun, &SPIN, roll);
+        assert_eq!(spun, after_spins(start, &SPIN, n + 1, roll));
+    }
+
+    #[test]
+    fn test_after_many_spins_matches_spinning() {
+        let start = grid("O.#..\n.O..O\n#..O.\nO...#\n.#O..");
+        let mut spun = start.clone();
+        for n in 1..=30 {
+            spin(&mut spun, &SPIN, roll);
+            assert_eq!(spun, after_spins(start.clone(), &SPIN, n, roll), "spin {n}");
+        }
+    }
+}
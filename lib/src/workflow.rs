@@ -0,0 +1,363 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::range::{Range, RangeContains};
+
+/// What a [`Rule`] checks of a named attribute
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Condition {
+    Always,
+    Less(String, usize),
+    Greater(String, usize),
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Always => Ok(()),
+            Condition::Less(attr, n) => write!(f, "{attr}<{n}"),
+            Condition::Greater(attr, n) => write!(f, "{attr}>{n}"),
+        }
+    }
+}
+
+/// Sends whatever meets `condition` on to `target`, either another workflow or a terminal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub condition: Condition,
+    pub target: String,
+}
+
+impl Rule {
+    pub fn new(condition: Condition, target: &str) -> Self {
+        Rule {
+            condition,
+            target: target.to_owned(),
+        }
+    }
+
+    pub fn always(target: &str) -> Self {
+        Self::new(Condition::Always, target)
+    }
+}
+
+/// Something which can be passed through [`Workflows`]
+pub trait Subject: Sized {
+    /// The part meeting `condition` and the part which doesn't, either of which may be nothing
+    fn split(self, condition: &Condition) -> (Option<Self>, Option<Self>);
+}
+
+/// A single item with a value for each attribute,
+/// a condition on an attribute it doesn't have is never met
+pub type Record = HashMap<String, usize>;
+
+impl Subject for Record {
+    fn split(self, condition: &Condition) -> (Option<Self>, Option<Self>) {
+        let met = match condition {
+            Condition::Always => true,
+            Condition::Less(attr, n) => self.get(attr).is_some_and(|v| v < n),
+            Condition::Greater(attr, n) => self.get(attr).is_some_and(|v| v > n),
+        };
+        if met {
+            (Some(self), None)
+        } else {
+            (None, Some(self))
+        }
+    }
+}
+
+/// Every item whose attributes each fall within a range, a hyper-rectangle.
+/// A condition on an attribute it doesn't have is never met.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    bounds: HashMap<String, Range>,
+}
+
+impl Region {
+    /// Each of `attributes` covering `range`
+    pub fn new(attributes: &[&str], range: Range) -> Self {
+        Region {
+            bounds: attributes
+                .iter()
+                .map(|attr| (attr.to_string(), range))
+                .collect(),
+        }
+    }
+
+    pub fn bound(&self, attribute: &str) -> Option<&Range> {
+        self.bounds.get(attribute)
+    }
+
+    /// How many items are within the region
+    pub fn volume(&self) -> usize {
+        self.bounds.values().map(RangeContains::length).product()
+    }
+
+    /// Copies of the region with `attribute` limited to `[from, split)` and `[split, to)`
+    fn cut(self, attribute: &str, split: usize) -> (Option<Self>, Option<Self>) {
+        let Some(r) = self.bounds.get(attribute).copied() else {
+            return (None, Some(self));
+        };
+        let with = |from: usize, to: usize| {
+            (from < to).then(|| {
+                let mut region = self.clone();
+                region
+                    .bounds
+                    .insert(attribute.to_owned(), Range::new(from, to));
+                region
+            })
+        };
+        (
+            with(r.from(), r.to().min(split)),
+            with(r.from().max(split), r.to()),
+        )
+    }
+}
+
+impl Subject for Region {
+    fn split(self, condition: &Condition) -> (Option<Self>, Option<Self>) {
+        match condition {
+            Condition::Always => (Some(self), None),
+            Condition::Less(attr, n) => self.cut(attr, *n),
+            Condition::Greater(attr, n) => {
+                let (below, above) = self.cut(attr, n + 1);
+                (above, below)
+            }
+        }
+    }
+}
+
+/// Named lists of rules, each sending items on to the first rule they meet.
+///
+/// Targets which aren't workflows are terminals, where items end up.
+/// Anything meeting none of a workflow's rules goes nowhere.
+///
+/// ```
+/// use aoclib::{range::Range, workflow::*};
+///
+/// let mut workflows = Workflows::new();
+/// workflows.add("in", vec![Rule::new(Condition::Less("x".into(), 10), "A"), Rule::always("R")]);
+///
+/// let record: Record = [("x".to_owned(), 3)].into();
+/// assert_eq!(Some("A".to_owned()), workflows.evaluate("in", record));
+///
+/// let volumes = workflows.volumes("in", Region::new(&["x", "y"], Range::new(0, 20)));
+/// assert_eq!(10 * 20, volumes["A"]);
+/// assert_eq!(10 * 20, volumes["R"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Workflows {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+    rules: Vec<Vec<Rule>>,
+}
+
+impl Workflows {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the workflow `name`, replacing any already called that
+    pub fn add(&mut self, name: &str, rules: Vec<Rule>) {
+        match self.ids.get(name) {
+            Some(id) => self.rules[*id] = rules,
+            None => {
+                self.ids.insert(name.to_owned(), self.names.len());
+                self.names.push(name.to_owned());
+                self.rules.push(rules);
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[Rule]> {
+        self.ids.get(name).map(|id| &self.rules[*id][..])
+    }
+
+    /// Every workflow in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Rule])> {
+        self.names
+            .iter()
+            .zip(&self.rules)
+            .map(|(name, rules)| (name.as_str(), &rules[..]))
+    }
+
+    /// Passes `subject` through the workflows from `start`,
+    /// giving each terminal reached along with the part of `subject` which reached it.
+    ///
+    /// Workflows which lead back round to themselves never finish.
+    pub fn run<S: Subject>(&self, start: &str, subject: S) -> Vec<(String, S)> {
+        self.walk(start, subject, |_, _| {})
+    }
+
+    /// The terminal `record` ends up at, if any
+    pub fn evaluate(&self, start: &str, record: Record) -> Option<String> {
+        self.run(start, record)
+            .into_iter()
+            .next()
+            .map(|(terminal, _)| terminal)
+    }
+
+    /// How much of `region` ends up at each terminal
+    pub fn volumes(&self, start: &str, region: Region) -> HashMap<String, usize> {
+        let mut volumes = HashMap::new();
+        for (terminal, part) in self.run(start, region) {
+            *volumes.entry(terminal).or_insert(0) += part.volume();
+        }
+        volumes
+    }
+
+    /// The rules, by workflow and position within it, which nothing in
+    /// `region` starting from `start` ever meets
+    pub fn unreachable(&self, start: &str, region: Region) -> Vec<(&str, usize)> {
+        let mut met = HashSet::new();
+        self.walk(start, region, |workflow, rule| {
+            met.insert((workflow, rule));
+        });
+        self.rules
+            .iter()
+            .enumerate()
+            .flat_map(|(id, rules)| (0..rules.len()).map(move |rule| (id, rule)))
+            .filter(|key| !met.contains(key))
+            .map(|(id, rule)| (self.names[id].as_str(), rule))
+            .collect()
+    }
+
+    fn walk<S, F>(&self, start: &str, subject: S, mut on_met: F) -> Vec<(String, S)>
+    where
+        S: Subject,
+        F: FnMut(usize, usize),
+    {
+        let mut ended = vec![];
+        let mut todo = vec![(start.to_owned(), subject)];
+        while let Some((at, subject)) = todo.pop() {
+            let Some(id) = self.ids.get(&at).copied() else {
+                ended.push((at, subject));
+                continue;
+            };
+            let mut rest = Some(subject);
+            for (i, rule) in self.rules[id].iter().enumerate() {
+                let Some(subject) = rest.take() else {
+                    break;
+                };
+                let (met, unmet) = subject.split(&rule.condition);
+                if let Some(met) = met {
+                    on_met(id, i);
+                    todo.push((rule.target.clone(), met));
+                }
+                rest = unmet;
+            }
+        }
+        ended
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn less(attr: &str, n: usize, target: &str) -> Rule {
+        Rule::new(Condition::Less(attr.to_owned(), n), target)
+    }
+
+    fn greater(attr: &str, n: usize, target: &str) -> Rule {
+        Rule::new(Condition::Greater(attr.to_owned(), n), target)
+    }
+
+    fn record(values: &[(&str, usize)]) -> Record {
+        values.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    /// accepts x below 10 and, of the rest, y above 5
+    fn example() -> Workflows {
+        let mut w = Workflows::new();
+        w.add("in", vec![less("x", 10, "A"), Rule::always("ys")]);
+        w.add("ys", vec![greater("y", 5, "A"), Rule::always("R")]);
+        w
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let w = example();
+        let evaluate = |x, y| w.evaluate("in", record(&[("x", x), ("y", y)]));
+        assert_eq!(Some("A".to_owned()), evaluate(9, 0));
+        assert_eq!(Some("R".to_owned()), evaluate(10, 5));
+        assert_eq!(Some("A".to_owned()), evaluate(10, 6));
+    }
+
+    #[test]
+    fn test_missing_attribute_is_never_met() {
+        let w = example();
+        assert_eq!(Some("A".to_owned()), w.evaluate("in", record(&[("y", 6)])));
+        assert_eq!(Some("R".to_owned()), w.evaluate("in", record(&[])));
+    }
+
+    #[test]
+    fn test_volumes() {
+        let w = example();
+        let region = Region::new(&["x", "y"], Range::new(0, 20));
+        let volumes = w.volumes("in", region.clone());
+        assert_eq!(10 * 20 + 10 * 14, volumes["A"]);
+        assert_eq!(10 * 6, volumes["R"]);
+        assert_eq!(region.volume(), volumes.values().sum::<usize>());
+    }
+
+    #[test]
+    fn test_run_splits_region() {
+        let w = example();
+        let parts = w.run("in", Region::new(&["x", "y"], Range::new(0, 20)));
+        let rejected: Vec<_> = parts.iter().filter(|(t, _)| t == "R").collect();
+        assert_eq!(1, rejected.len());
+        let region = &rejected[0].1;
+        assert_eq!(Some(&Range::new(10, 20)), region.bound("x"));
+        assert_eq!(Some(&Range::new(0, 6)), region.bound("y"));
+    }
+
+    #[test]
+    fn test_region_and_records_agree() {
+        let w = example();
+        let mut accepted = 0;
+        for x in 0..20 {
+            for y in 0..20 {
+                if w.evaluate("in", record(&[("x", x), ("y", y)])) == Some("A".to_owned()) {
+                    accepted += 1;
+                }
+            }
+        }
+        let volumes = w.volumes("in", Region::new(&["x", "y"], Range::new(0, 20)));
+        assert_eq!(accepted, volumes["A"]);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let mut w = example();
+        let region = Region::new(&["x", "y"], Range::new(0, 20));
+        assert!(w.unreachable("in", region.clone()).is_empty());
+
+        // x<5 is already taken by x<10 and nothing leads to "orphan"
+        w.add(
+            "in",
+            vec![less("x", 10, "A"), less("x", 5, "R"), Rule::always("ys")],
+        );
+        w.add("orphan", vec![Rule::always("A")]);
+        assert_eq!(
+            vec![("in", 1), ("orphan", 0)],
+            w.unreachable("in", region.clone())
+        );
+
+        // nothing in the region has a y above 50
+        w.add("ys", vec![greater("y", 50, "A"), Rule::always("R")]);
+        assert_eq!(
+            vec![("in", 1), ("ys", 0), ("orphan", 0)],
+            w.unreachable("in", region)
+        );
+    }
+
+    #[test]
+    fn test_condition_display() {
+        assert_eq!("a<2006", Condition::Less("a".into(), 2006).to_string());
+        assert_eq!("m>2090", Condition::Greater("m".into(), 2090).to_string());
+        assert_eq!("", Condition::Always.to_string());
+    }
+}
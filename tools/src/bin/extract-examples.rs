@@ -0,0 +1,40 @@
+use std::{env, fs, path::PathBuf, process};
+
+use aoc_tools::examples;
+
+/// Usage: `extract-examples <saved puzzle.html> <day dir>`
+///
+/// Writes each part's example into the day as a fixture and prints
+/// a `tests` module checking the example answers.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [html, day_dir] = &args[..] else {
+        eprintln!("usage: extract-examples <puzzle.html> <day dir>");
+        process::exit(2);
+    };
+
+    let html = fs::read_to_string(html).unwrap_or_else(|e| {
+        eprintln!("can't read {html}: {e}");
+        process::exit(1);
+    });
+    let parts = examples::extract(&html);
+    if parts.iter().all(|p| p.examples.is_empty()) {
+        eprintln!("no <pre><code> examples found");
+        process::exit(1);
+    }
+
+    let day_dir = PathBuf::from(day_dir);
+    match examples::write_fixtures(&day_dir, &parts) {
+        Ok(written) => {
+            for name in written {
+                eprintln!("wrote {}", day_dir.join(name).display());
+            }
+        }
+        Err(e) => {
+            eprintln!("can't write fixtures: {e}");
+            process::exit(1);
+        }
+    }
+
+    print!("{}", examples::test_skeleton(&parts));
+}
@@ -0,0 +1,46 @@
+use std::{env, fs, path::PathBuf, process};
+
+use aoc_tools::{examples, scaffold};
+
+/// Usage: `new-day <year dir> <day> [saved puzzle.html]`
+///
+/// Creates `dN/main.rs` and its fixtures in the year and registers its `[[bin]]`,
+/// taking examples and tests from the puzzle page when one is given.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (year_dir, day, puzzle) = match &args[..] {
+        [year_dir, day] => (year_dir, day, None),
+        [year_dir, day, puzzle] => (year_dir, day, Some(puzzle)),
+        _ => {
+            eprintln!("usage: new-day <year dir> <day> [puzzle.html]");
+            process::exit(2);
+        }
+    };
+    let day: u8 = match day.parse() {
+        Ok(d) if (1..=25).contains(&d) => d,
+        _ => {
+            eprintln!("day must be 1 to 25, not {day}");
+            process::exit(2);
+        }
+    };
+
+    let parts = puzzle.map(|p| {
+        let html = fs::read_to_string(p).unwrap_or_else(|e| {
+            eprintln!("can't read {p}: {e}");
+            process::exit(1);
+        });
+        examples::extract(&html)
+    });
+
+    match scaffold::create_day(&PathBuf::from(year_dir), day, parts.as_deref()) {
+        Ok(created) => {
+            for path in created {
+                println!("created {}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::{fs, io, path::Path};
+
+use regex::Regex;
+
+/// The examples and answer found in one part of a puzzle description
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Part {
+    /// The text of each `<pre><code>` block, in the order they appear
+    pub examples: Vec<String>,
+    /// The last emphasised code in the part, which is where the example's answer is given
+    pub answer: Option<String>,
+}
+
+impl Part {
+    /// The type the part should return to be compared with its answer,
+    /// `usize` when there is no answer to go on
+    pub fn answer_type(&self) -> &'static str {
+        match self.answer.as_deref() {
+            Some(a) if a.parse::<usize>().is_ok() => "usize",
+            Some(a) if a.parse::<i64>().is_ok() => "i64",
+            Some(_) => "String",
+            None => "usize",
+        }
+    }
+}
+
+/// Finds the examples of each part within a saved puzzle page.
+///
+/// Each part is an `<article>`, a page without any is treated as a single part.
+pub fn extract(html: &str) -> Vec<Part> {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let pre = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answer =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let articles: Vec<&str> = article
+        .captures_iter(html)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect();
+    let articles = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    articles
+        .into_iter()
+        .map(|a| Part {
+            examples: pre
+                .captures_iter(a)
+                .map(|c| decode(&strip_tags(&c[1])))
+                .collect(),
+            answer: answer
+                .captures_iter(a)
+                .last()
+                .and_then(|c| c.get(1).or_else(|| c.get(2)))
+                .map(|m| decode(&strip_tags(m.as_str()))),
+        })
+        .collect()
+}
+
+fn strip_tags(html: &str) -> String {
+    Regex::new(r"<[^>]*>")
+        .unwrap()
+        .replace_all(html, "")
+        .into_owned()
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The fixture file each part's tests read, following the repo's naming:
+/// part 2 only gets its own file when its example differs from part 1's
+pub fn fixtures(parts: &[Part]) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    for (i, part) in parts.iter().enumerate() {
+        let Some(example) = part.examples.first() else {
+            continue;
+        };
+        if files.iter().any(|(_, existing)| existing == example) {
+            continue;
+        }
+        let name = match i {
+            0 => "input.test.txt".to_owned(),
+            n => format!("input{}.test.txt", n + 1),
+        };
+        files.push((name, example.clone()));
+    }
+    files
+}
+
+/// Writes the fixtures into `day_dir`, leaving any that already exist alone.
+/// Returns the names of the files written.
+pub fn write_fixtures(day_dir: &Path, parts: &[Part]) -> io::Result<Vec<String>> {
+    let mut written = Vec::new();
+    for (name, contents) in fixtures(parts) {
+        let path = day_dir.join(&name);
+        if path.exists() {
+            continue;
+        }
+        fs::write(path, contents)?;
+        written.push(name);
+    }
+    Ok(written)
+}
+
+/// A `tests` module asserting each part's answer against its example,
+/// the assertion is left commented out for a part without an answer
+pub fn test_skeleton(parts: &[Part]) -> String {
+    let files = fixtures(parts);
+    let mut tests = Vec::new();
+    for (i, part) in parts.iter().enumerate().take(2) {
+        let n = i + 1;
+        // a part without its own example uses the first one
+        let fixture = part
+            .examples
+            .first()
+            .and_then(|e| files.iter().find(|(_, contents)| contents == e))
+            .or(files.first())
+            .map(|(name, _)| name.as_str())
+            .unwrap_or("input.test.txt");
+        let check = format!("part{n}(include_str!(\"{fixture}\"))");
+        let body = match part.answer.as_deref() {
+            Some(a) if part.answer_type() == "String" => format!("assert_eq!({a:?}, {check});"),
+            Some(a) => format!("assert_eq!({a}, {check});"),
+            None => format!(
+                "// no answer was found for this example\n        // assert_eq!(?, {check});"
+            ),
+        };
+        tests.push(format!(
+            "    #[test]\n    fn test_example_p{n}() {{\n        {body}\n    }}\n"
+        ));
+    }
+    format!(
+        "#[cfg(test)]\nmod tests {{\n    use crate::*;\n\n{}}}\n",
+        tests.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54601</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree &lt;x&gt; &amp;
+</code></pre>
+<p>Adding these together produces <code><em>281</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        let parts = extract(PAGE);
+        assert_eq!(2, parts.len());
+        assert_eq!(vec!["1abc2\npqr3stu8vwx\n"], parts[0].examples);
+        assert_eq!(Some("142".to_owned()), parts[0].answer);
+        assert_eq!(vec!["two1nine\neightwothree <x> &\n"], parts[1].examples);
+        assert_eq!(Some("281".to_owned()), parts[1].answer);
+    }
+
+    #[test]
+    fn test_page_without_articles() {
+        let parts = extract("<pre><code>a\n</code></pre><p><em><code>CMZ</code></em></p>");
+        assert_eq!(
+            vec![Part {
+                examples: vec!["a\n".to_owned()],
+                answer: Some("CMZ".to_owned())
+            }],
+            parts
+        );
+    }
+
+    #[test]
+    fn test_fixtures_share_repeated_examples() {
+        let part = |e: &str, a: &str| Part {
+            examples: vec![e.to_owned()],
+            answer: Some(a.to_owned()),
+        };
+        let parts = vec![part("same", "1"), part("same", "2")];
+        assert_eq!(
+            vec![("input.test.txt".to_owned(), "same".to_owned())],
+            fixtures(&parts)
+        );
+
+        let parts = vec![part("one", "1"), part("two", "2")];
+        assert_eq!(
+            vec!["input.test.txt", "input2.test.txt"],
+            fixtures(&parts)
+                .iter()
+                .map(|(n, _)| n.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_skeleton_for_both_parts() {
+        let parts = extract(PAGE);
+        let expected = r#"#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_example_p1() {
+        assert_eq!(142, part1(include_str!("input.test.txt")));
+    }
+
+    #[test]
+    fn test_example_p2() {
+        assert_eq!(281, part2(include_str!("input2.test.txt")));
+    }
+}
+"#;
+        assert_eq!(expected, test_skeleton(&parts));
+    }
+
+    #[test]
+    fn test_skeleton_without_part_two() {
+        let parts =
+            extract("<article><pre><code>x</code></pre><code><em>CMZ</em></code></article>");
+        let skeleton = test_skeleton(&parts);
+        assert!(skeleton.contains(r#"assert_eq!("CMZ", part1(include_str!("input.test.txt")));"#));
+        assert!(!skeleton.contains("part2"));
+    }
+
+    #[test]
+    fn test_skeleton_without_answer() {
+        let parts = extract("<article><pre><code>x</code></pre></article>");
+        let skeleton = test_skeleton(&parts);
+        assert!(skeleton
+            .contains(r#"        // assert_eq!(?, part1(include_str!("input.test.txt")));"#));
+        assert!(!skeleton.contains("todo!"));
+    }
+
+    #[test]
+    fn test_answer_type() {
+        let part = |a: Option<&str>| Part {
+            examples: vec![],
+            answer: a.map(str::to_owned),
+        };
+        assert_eq!("usize", part(Some("142")).answer_type());
+        assert_eq!("i64", part(Some("-3")).answer_type());
+        assert_eq!("String", part(Some("CMZ")).answer_type());
+        assert_eq!("usize", part(None).answer_type());
+    }
+
+    #[test]
+    fn test_write_fixtures_keeps_existing() {
+        let dir = std::env::temp_dir().join(format!("aoc-tools-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.test.txt"), "hand written").unwrap();
+
+        let written = write_fixtures(&dir, &extract(PAGE)).unwrap();
+        assert_eq!(vec!["input2.test.txt"], written);
+        assert_eq!(
+            "hand written",
+            fs::read_to_string(dir.join("input.test.txt")).unwrap()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
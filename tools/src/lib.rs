@@ -0,0 +1,4 @@
+//! Helpers for starting a new day, used by the binaries in `src/bin`.
+
+pub mod examples;
+pub mod scaffold;
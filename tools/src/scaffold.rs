@@ -0,0 +1,236 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::examples::{self, Part};
+
+const MAIN_TEMPLATE: &str = r#"use std::time::Instant;
+
+fn main() {
+    let input = include_str!("input.txt");
+    let now = Instant::now();
+    println!("part1: {}", part1(input));
+    println!("part2: {}", part2(input));
+    println!("{}ms", now.elapsed().as_millis());
+}
+
+"#;
+
+/// Tests for a day without a puzzle description, which pass against the stubbed parts
+const DEFAULT_TESTS: &str = r#"#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_example_p1() {
+        assert_eq!(0, part1(include_str!("input.test.txt")));
+    }
+}
+"#;
+
+/// The `main.rs` every day starts from, with each part stubbed to return
+/// the given type, followed by `tests`
+pub fn main_rs(answer_types: [&str; 2], tests: &str) -> String {
+    let parts: String = answer_types
+        .iter()
+        .enumerate()
+        .map(|(i, answer_type)| {
+            let value = match *answer_type {
+                "String" => "String::new()",
+                _ => "0",
+            };
+            format!(
+                "fn part{}(_txt: &str) -> {answer_type} {{\n    {value}\n}}\n\n",
+                i + 1
+            )
+        })
+        .collect();
+    format!("{MAIN_TEMPLATE}{parts}{tests}")
+}
+
+/// `cargo_toml` with a `[[bin]]` for `name` added to the end
+pub fn register_bin(cargo_toml: &str, name: &str) -> Result<String, String> {
+    if cargo_toml
+        .lines()
+        .any(|l| l.trim() == format!("name = \"{name}\""))
+    {
+        return Err(format!("{name} is already registered"));
+    }
+    Ok(format!(
+        "{}\n\n[[bin]]\nname = \"{name}\"\npath = \"{name}/main.rs\"\n",
+        cargo_toml.trim_end()
+    ))
+}
+
+/// Creates `dN` within `year_dir` and registers it in the year's `Cargo.toml`,
+/// with fixtures and tests from `puzzle` when there is one.
+///
+/// Refuses to touch a day which already exists. Returns the files created.
+pub fn create_day(
+    year_dir: &Path,
+    day: u8,
+    puzzle: Option<&[Part]>,
+) -> Result<Vec<PathBuf>, String> {
+    let name = format!("d{day}");
+    let day_dir = year_dir.join(&name);
+    if day_dir.exists() {
+        return Err(format!("{} already exists", day_dir.display()));
+    }
+    let manifest = year_dir.join("Cargo.toml");
+    let cargo_toml = fs::read_to_string(&manifest)
+        .map_err(|e| format!("can't read {}: {e}", manifest.display()))?;
+    let cargo_toml = register_bin(&cargo_toml, &name)?;
+
+    let tests = match puzzle {
+        Some(parts) => examples::test_skeleton(parts),
+        None => DEFAULT_TESTS.to_owned(),
+    };
+    let answer_type = |i: usize| {
+        puzzle
+            .and_then(|parts| parts.get(i))
+            .map_or("usize", Part::answer_type)
+    };
+
+    let write_err = |path: &Path, e: std::io::Error| format!("can't write {}: {e}", path.display());
+    fs::create_dir_all(&day_dir).map_err(|e| write_err(&day_dir, e))?;
+
+    let mut created = Vec::new();
+    let main = day_dir.join("main.rs");
+    fs::write(&main, main_rs([answer_type(0), answer_type(1)], &tests))
+        .map_err(|e| write_err(&main, e))?;
+    created.push(main);
+
+    if let Some(parts) = puzzle {
+        let written =
+            examples::write_fixtures(&day_dir, parts).map_err(|e| write_err(&day_dir, e))?;
+        created.extend(written.into_iter().map(|n| day_dir.join(n)));
+    }
+    // the real input is fetched separately, these just let the day compile
+    for fixture in ["input.txt", "input.test.txt"] {
+        let path = day_dir.join(fixture);
+        if !path.exists() {
+            fs::write(&path, "").map_err(|e| write_err(&path, e))?;
+            created.push(path);
+        }
+    }
+
+    fs::write(&manifest, cargo_toml).map_err(|e| write_err(&manifest, e))?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_year(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-tools-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"aoc2099\"\n\n[[bin]]\nname = \"d1\"\npath = \"d1/main.rs\"\n\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_register_bin() {
+        let toml = "[[bin]]\nname = \"d1\"\npath = \"d1/main.rs\"\n\n\n";
+        assert_eq!(
+            Ok("[[bin]]\nname = \"d1\"\npath = \"d1/main.rs\"\n\n[[bin]]\nname = \"d2\"\npath = \"d2/main.rs\"\n".to_owned()),
+            register_bin(toml, "d2")
+        );
+        assert_eq!(
+            Err("d1 is already registered".to_owned()),
+            register_bin(toml, "d1")
+        );
+        // d1 shouldn't be mistaken for d10
+        assert!(register_bin(toml, "d10").is_ok());
+    }
+
+    #[test]
+    fn test_create_day() {
+        let year = temp_year("new-day");
+        let created = create_day(&year, 2, None).unwrap();
+        assert_eq!(
+            vec![
+                year.join("d2/main.rs"),
+                year.join("d2/input.txt"),
+                year.join("d2/input.test.txt")
+            ],
+            created
+        );
+
+        let main = fs::read_to_string(year.join("d2/main.rs")).unwrap();
+        assert!(main.starts_with("use std::time::Instant;"));
+        assert!(main.contains("fn part2(_txt: &str) -> usize {"));
+        assert!(main.ends_with(DEFAULT_TESTS));
+
+        let toml = fs::read_to_string(year.join("Cargo.toml")).unwrap();
+        assert!(toml.ends_with("[[bin]]\nname = \"d2\"\npath = \"d2/main.rs\"\n"));
+
+        fs::remove_dir_all(&year).unwrap();
+    }
+
+    #[test]
+    fn test_create_day_from_puzzle() {
+        let year = temp_year("new-day-puzzle");
+        let parts = examples::extract(
+            "<article><pre><code>1\n2\n</code></pre><code><em>3</em></code></article>",
+        );
+        create_day(&year, 3, Some(&parts)).unwrap();
+
+        assert_eq!(
+            "1\n2\n",
+            fs::read_to_string(year.join("d3/input.test.txt")).unwrap()
+        );
+        let main = fs::read_to_string(year.join("d3/main.rs")).unwrap();
+        assert!(main.contains(r#"assert_eq!(3, part1(include_str!("input.test.txt")));"#));
+
+        fs::remove_dir_all(&year).unwrap();
+    }
+
+    #[test]
+    fn test_create_day_with_text_answer() {
+        let year = temp_year("new-day-text");
+        let parts = examples::extract(
+            "<article><pre><code>[D]\n</code></pre><code><em>CMZ</em></code></article>\
+             <article><pre><code>[D]\n</code></pre><code><em>12</em></code></article>",
+        );
+        create_day(&year, 5, Some(&parts)).unwrap();
+
+        let main = fs::read_to_string(year.join("d5/main.rs")).unwrap();
+        assert!(main.contains("fn part1(_txt: &str) -> String {\n    String::new()\n}"));
+        assert!(main.contains("fn part2(_txt: &str) -> usize {\n    0\n}"));
+        assert!(main.contains(r#"assert_eq!("CMZ", part1(include_str!("input.test.txt")));"#));
+        assert!(main.contains(r#"assert_eq!(12, part2(include_str!("input.test.txt")));"#));
+
+        fs::remove_dir_all(&year).unwrap();
+    }
+
+    #[test]
+    fn test_refuses_existing_day() {
+        let year = temp_year("new-day-existing");
+        fs::create_dir_all(year.join("d1")).unwrap();
+        fs::write(year.join("d1/main.rs"), "mine").unwrap();
+        let before = fs::read_to_string(year.join("Cargo.toml")).unwrap();
+
+        assert!(create_day(&year, 1, None)
+            .unwrap_err()
+            .ends_with("d1 already exists"));
+        assert_eq!("mine", fs::read_to_string(year.join("d1/main.rs")).unwrap());
+
+        // registered but missing its directory
+        fs::remove_dir_all(year.join("d1")).unwrap();
+        assert_eq!(
+            Err("d1 is already registered".to_owned()),
+            create_day(&year, 1, None)
+        );
+        assert!(!year.join("d1").exists());
+        assert_eq!(before, fs::read_to_string(year.join("Cargo.toml")).unwrap());
+
+        fs::remove_dir_all(&year).unwrap();
+    }
+}